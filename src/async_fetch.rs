@@ -0,0 +1,106 @@
+//! Fetching from async code.
+
+use std::time::{Duration, Instant, SystemTime};
+
+use tokio::net::{lookup_host, ToSocketAddrs, UdpSocket};
+
+use crate::{any_addr, parse_unconnected_pong, same_addr, MotdError, MotdErrorCode, Result, UnconnectedPong, DEFAULT_BUFFER_SIZE, UNCONNECTED_PING};
+
+/// Async version of [`fetch_unconected_pong_timeout`](crate::fetch_unconected_pong_timeout) built on tokio, so waiting for the server doesn't block the executor.
+/// Pong is parsed exactly like in the sync version.
+///
+/// # Arguments
+///
+/// * `addr` - address of the target server.
+/// * `timeout` - how long to wait for the response.
+///
+/// # Panics
+///
+/// Function can return the same errors as *fetch_unconected_pong_timeout*.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use mcpe_motd::fetch_unconected_pong_async;
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let pong = fetch_unconected_pong_async("127.0.0.1:19132", Duration::from_secs(1)).await?;
+///
+///     println!("{}", pong.server_id_string_parsed.motd);
+///     Ok(())
+/// }
+/// ```
+pub async fn fetch_unconected_pong_async(addr: impl ToSocketAddrs, timeout: Duration) -> Result<UnconnectedPong> {
+    let result = fetch(addr, timeout).await;
+
+    if let Err(e) = &result {
+        warn!("fetch failed: code={:?} message={}", e.code, e.message);
+    }
+
+    result
+}
+
+/// Does the actual fetch, errors are logged by the caller.
+async fn fetch(addr: impl ToSocketAddrs, timeout: Duration) -> Result<UnconnectedPong> {
+    let addr = match lookup_host(addr).await {
+        Ok(mut addrs) => match addrs.next() {
+            Some(addr) => addr,
+            None => { return Err(MotdError::new(MotdErrorCode::CantResolveAddress, String::from("Address didn't resolve to anything"))); }
+        },
+        Err(e) => { return Err(MotdError::new(MotdErrorCode::CantResolveAddress, format!("Couldn't resolve address: {}", e))); }
+    };
+
+    let bind_addr = any_addr(addr);
+
+    let socket = match UdpSocket::bind(bind_addr).await {
+        Ok(sock) => sock,
+        Err(e) => { return Err(MotdError::new(MotdErrorCode::CantBind, format!("Couldn't bind to {}: {}", bind_addr, e))); }
+    };
+
+    let sent_at = Instant::now();
+
+    match socket.send_to(&UNCONNECTED_PING, addr).await {
+        Ok(_) => (),
+        Err(e) => { return Err(MotdError::new(MotdErrorCode::CantSendTo, format!("Couldn't send to {}: {}", addr, e))); }
+    }
+
+    debug!("sent ping: addr={} bytes={}", addr, UNCONNECTED_PING.len());
+
+    let mut response = vec![0; DEFAULT_BUFFER_SIZE];
+
+    let deadline = tokio::time::Instant::now() + timeout;
+
+    let (size, src) = loop {
+        let (size, src) = match tokio::time::timeout_at(deadline, socket.recv_from(&mut response)).await {
+            Ok(Ok(v)) => v,
+            Ok(Err(e)) => { return Err(MotdError::new(MotdErrorCode::CantReceive, format!("Couldn't receive from {}: {}", addr, e))); }
+            Err(_) => { return Err(MotdError::new(MotdErrorCode::Timeout, format!("{} didn't respond within {:?}", addr, timeout))); }
+        };
+
+        // Packets from other addresses aren't answers to this ping
+        if same_addr(src, addr) {
+            debug!("received packet: addr={} bytes={}", addr, size);
+            break (size, src);
+        }
+
+        debug!("ignored stray packet: addr={} src={} bytes={}", addr, src, size);
+    };
+
+    let latency = sent_at.elapsed();
+    let received_at = SystemTime::now();
+
+    // Datagrams longer than the buffer are silently cut off
+    if size == response.len() {
+        return Err(MotdError::new(MotdErrorCode::PacketTruncated, format!("Response of {} filled the whole {} byte buffer", addr, size)));
+    }
+
+    let mut pong = parse_unconnected_pong(&response[..size])?;
+
+    pong.source_addr = Some(src);
+    pong.latency = latency;
+    pong.received_at = received_at;
+
+    Ok(pong)
+}
@@ -0,0 +1,168 @@
+//! Parsing server id strings without allocating.
+
+use std::str::{FromStr, Split};
+
+use crate::{quote, MotdError, MotdErrorCode, ParseWarning, Result, DEFAULT_PORT};
+
+/// Same as [`ServerIdStringParsed`](crate::ServerIdStringParsed), but strings are borrowed from the raw server id string instead of being allocated,
+/// returned by [`parse_server_id_string_ref`]. Missing optional fields are replaced with the same default ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServerIdStringRef<'a> {
+    /// Server minecraft edition (MCPE or MCEE), as server sent it.
+    pub edition: &'a str,
+    /// Text that is displayed in the server tab.
+    pub motd: &'a str,
+    /// Minecraft protocol version (e.g. 615).
+    pub protocol_version: u16,
+    /// Minecraft version name (e.g. 1.20.30).
+    pub version_name: &'a str,
+    /// How many players is playing on the server.
+    pub player_count: i32,
+    /// How many players can be playing on the server at the same time.
+    pub max_player_count: i32,
+    /// Some unique id.
+    pub server_unique_id: &'a str,
+    /// Map name, server list shows it as the second motd line.
+    pub level_name: &'a str,
+    /// Default gamemode.
+    pub gamemode: &'a str,
+    /// Default gamemode but number.
+    pub gamemode_numeric: u8,
+    /// Port used for IPv4 communication.
+    pub port_v4: u16,
+    /// Port used for IPv6 communication.
+    pub port_v6: u16,
+}
+
+/// Same as [`parse_server_id_string`](crate::parse_server_id_string), but doesn't allocate: strings of the result point into `raw`.
+/// Useful for scanners that parse a lot of pongs and only read a few fields of each.
+/// Fields after *port_v6* are ignored.
+///
+/// # Arguments
+///
+/// * `raw` - raw server id string (e.g. `MCPE;Dedicated Server;615;1.20.30;0;10;...`).
+///
+/// # Panics
+///
+/// Function can return the same errors as *parse_server_id_string*.
+///
+/// # Example
+///
+/// ```
+/// use mcpe_motd::parse_server_id_string_ref;
+///
+/// let raw = String::from("MCPE;Dedicated Server;615;1.20.30;3;10");
+/// let parsed = parse_server_id_string_ref(&raw).unwrap();
+///
+/// assert_eq!(parsed.motd, "Dedicated Server");
+/// assert_eq!(parsed.player_count, 3);
+/// assert_eq!(parsed.gamemode, "Survival");
+/// assert_eq!(parsed.port_v4, 19132);
+///
+/// let parsed = parse_server_id_string_ref("MCPE;Dedicated Server; 615 ;1.20.30; 3 ;10").unwrap();
+/// assert_eq!((parsed.protocol_version, parsed.player_count), (615, 3));
+///
+/// // Empty required field is an error for both parsers
+/// use mcpe_motd::{parse_server_id_string, MotdErrorCode};
+///
+/// let raw = "MCPE;Dedicated Server;;1.20.30;3;10";
+/// assert_eq!(parse_server_id_string_ref(raw).unwrap_err().code, MotdErrorCode::CantParseProtocolVersion);
+/// assert_eq!(parse_server_id_string(raw).unwrap_err().code, MotdErrorCode::CantParseProtocolVersion);
+/// ```
+pub fn parse_server_id_string_ref(raw: &str) -> Result<ServerIdStringRef<'_>> {
+    Ok(split_fields(raw, false, |_| ())?.parsed)
+}
+
+/// Server id string split by [`split_fields`], shared by both parsers.
+pub(crate) struct SplitFields<'a> {
+    /// Known fields, missing optional ones replaced with defaults.
+    pub(crate) parsed: ServerIdStringRef<'a>,
+    /// How many of the 12 known fields server id string had.
+    pub(crate) known_count: usize,
+    /// Fields after *port_v6*.
+    pub(crate) extra_fields: Split<'a, char>,
+}
+
+/// Splits `raw` and parses its known fields, calling `on_warning` for every optional field that was replaced with default.
+/// With `lenient` unparsable optional numbers are replaced with defaults too, instead of being an error.
+pub(crate) fn split_fields(raw: &str, lenient: bool, mut on_warning: impl FnMut(ParseWarning)) -> Result<SplitFields<'_>> {
+    // Fields are positional, so empty ones are kept (only the trailing `;` most servers send is dropped)
+    let mut fields = raw.strip_suffix(';').unwrap_or(raw).split(';');
+    let mut known_count = 0;
+
+    let mut next = || {
+        let field = fields.next();
+        known_count += usize::from(field.is_some());
+        field
+    };
+
+    let (edition, motd, protocol_version, version_name) = match (next(), next(), next(), next()) {
+        (Some(edition), Some(motd), Some(protocol_version), Some(version_name)) => (edition, motd, protocol_version, version_name),
+        _ => {
+            return Err(MotdError::new(MotdErrorCode::ServerIdStringTooSmall, format!("Server id string has less than 4 required fields: {}", quote(raw))));
+        }
+    };
+
+    let parsed = ServerIdStringRef {
+        edition,
+        motd,
+        // Required, so unlike the optional numbers an empty one is an error too
+        protocol_version: match protocol_version.trim().parse() {
+            Ok(v) => v,
+            Err(_) => {
+                return Err(MotdError::new(MotdErrorCode::CantParseProtocolVersion, String::from("Couldn't parse protocol_version field from server id string")));
+            }
+        },
+        version_name,
+        player_count: parse_number(next(), -1, &PLAYER_COUNT, lenient, &mut on_warning)?,
+        max_player_count: parse_number(next(), -1, &MAX_PLAYER_COUNT, lenient, &mut on_warning)?,
+        server_unique_id: optional_string(next(), "", ParseWarning::MissingServerUniqueId, &mut on_warning),
+        level_name: optional_string(next(), "", ParseWarning::MissingLevelName, &mut on_warning),
+        gamemode: optional_string(next(), "Survival", ParseWarning::MissingGamemode, &mut on_warning),
+        gamemode_numeric: parse_number(next(), 0, &GAMEMODE_NUMERIC, lenient, &mut on_warning)?,
+        port_v4: parse_number(next(), DEFAULT_PORT, &PORT_V4, lenient, &mut on_warning)?,
+        port_v6: parse_number(next(), DEFAULT_PORT, &PORT_V6, lenient, &mut on_warning)?,
+    };
+
+    Ok(SplitFields { parsed, known_count, extra_fields: fields })
+}
+
+/// How an optional number is reported when it's defaulted or can't be parsed.
+struct NumberField {
+    name: &'static str,
+    missing: ParseWarning,
+    invalid: ParseWarning,
+    code: MotdErrorCode,
+}
+
+const PLAYER_COUNT: NumberField = NumberField { name: "player_count", missing: ParseWarning::MissingPlayerCount, invalid: ParseWarning::InvalidPlayerCount, code: MotdErrorCode::CantParsePlayerCount };
+const MAX_PLAYER_COUNT: NumberField = NumberField { name: "max_player_count", missing: ParseWarning::MissingMaxPlayerCount, invalid: ParseWarning::InvalidMaxPlayerCount, code: MotdErrorCode::CantParsePlayerMaxCount };
+const GAMEMODE_NUMERIC: NumberField = NumberField { name: "gamemode_numeric", missing: ParseWarning::MissingGamemodeNumeric, invalid: ParseWarning::InvalidGamemodeNumeric, code: MotdErrorCode::CantParseGameModeNum };
+const PORT_V4: NumberField = NumberField { name: "port_v4", missing: ParseWarning::MissingPortV4, invalid: ParseWarning::InvalidPortV4, code: MotdErrorCode::CantParsePort4 };
+const PORT_V6: NumberField = NumberField { name: "port_v6", missing: ParseWarning::MissingPortV6, invalid: ParseWarning::InvalidPortV6, code: MotdErrorCode::CantParsePort6 };
+
+/// Parses `field` as a number ignoring surrounding whitespace, `default` if it's missing or empty (or invalid and `lenient`).
+fn parse_number<T: FromStr>(field: Option<&str>, default: T, spec: &NumberField, lenient: bool, on_warning: &mut impl FnMut(ParseWarning)) -> Result<T> {
+    match field.map(str::trim) {
+        Some(field) if !field.is_empty() => match field.parse() {
+            Ok(v) => Ok(v),
+            Err(_) if lenient => {
+                on_warning(spec.invalid);
+                Ok(default)
+            }
+            Err(_) => Err(MotdError::new(spec.code, format!("Couldn't parse {} field from server id string", spec.name))),
+        },
+        _ => {
+            on_warning(spec.missing);
+            Ok(default)
+        }
+    }
+}
+
+/// `field` as it is (even empty), `default` if it's missing.
+fn optional_string<'a>(field: Option<&'a str>, default: &'a str, missing: ParseWarning, on_warning: &mut impl FnMut(ParseWarning)) -> &'a str {
+    field.unwrap_or_else(|| {
+        on_warning(missing);
+        default
+    })
+}
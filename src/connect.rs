@@ -0,0 +1,118 @@
+//! Checking whether servers accept connections.
+
+use std::net::{ToSocketAddrs, UdpSocket};
+use std::time::{Duration, Instant};
+
+use crate::{any_addr, resolve, same_addr, MotdError, MotdErrorCode, Result, RAKNET_MAGIC};
+
+/// RakNet protocol version used by Bedrock.
+const RAKNET_PROTOCOL_VERSION: u8 = 11;
+
+/// Open connection request 1 is padded to the MTU client wants to use (1492 minus 28 bytes of IP and UDP headers).
+const OPEN_CONNECTION_REQUEST_1_SIZE: usize = 1464;
+
+/// Returns whether server answers [RakNet open connection request 1](https://wiki.vg/Raknet_Protocol#Open_Connection_Request_1)
+/// with open connection reply 1, i.e. whether the first step of joining would succeed.
+/// Unlike a pong, it means the RakNet layer of the server accepts connection requests and the MTU handshake succeeded.
+/// Free slots and bans are only checked later (at open connection request 2 and login), so joining may still fail.
+///
+/// If server says it uses another RakNet protocol version, request is sent once more with that version.
+/// None of the packets make the server allocate a connection, the handshake isn't continued.
+///
+/// # Arguments
+///
+/// * `addr` - address of the target server.
+/// * `timeout` - how long to wait for the reply (must not be zero).
+///
+/// # Panics
+///
+/// Function can return an error if:
+///  - couldn't bind a socket
+///  - `timeout` is zero (*MotdErrorCode::Timeout*)
+///  - couldn't send packet to the target server
+///
+/// Server that didn't reply in time or replied with anything else isn't connectable, that's not an error.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use mcpe_motd::check_connectable;
+///
+/// if check_connectable("127.0.0.1:19132", Duration::from_secs(1))? {
+///     println!("Server accepts connections");
+/// }
+/// # Ok::<(), mcpe_motd::MotdError>(())
+/// ```
+pub fn check_connectable(addr: impl ToSocketAddrs, timeout: Duration) -> Result<bool> {
+    let addr = resolve(addr)?;
+    let bind_addr = any_addr(addr);
+
+    let socket = match UdpSocket::bind(bind_addr) {
+        Ok(sock) => sock,
+        Err(e) => { return Err(MotdError::new(MotdErrorCode::CantBind, format!("Couldn't bind to {}: {}", bind_addr, e))); }
+    };
+
+    if timeout.is_zero() || socket.set_read_timeout(Some(timeout)).is_err() {
+        return Err(MotdError::new(MotdErrorCode::Timeout, format!("Couldn't wait for {} with timeout {:?}", addr, timeout)));
+    }
+
+    let mut protocol_version = RAKNET_PROTOCOL_VERSION;
+    let deadline = Instant::now() + timeout;
+
+    // Second attempt is made only with the version server asked for
+    for _ in 0..2 {
+        // Both attempts together wait at most `timeout`
+        let remaining = deadline.saturating_duration_since(Instant::now());
+
+        if remaining.is_zero() || socket.set_read_timeout(Some(remaining)).is_err() {
+            return Ok(false);
+        }
+
+        let request = build_open_connection_request_1(protocol_version);
+
+        match socket.send_to(&request, addr) {
+            Ok(_) => (),
+            Err(e) => { return Err(MotdError::new(MotdErrorCode::CantSendTo, format!("Couldn't send to {}: {}", addr, e))); }
+        }
+
+        let mut response = [0; 2048];
+
+        let size = loop {
+            match socket.recv_from(&mut response) {
+                Ok((size, src)) if same_addr(src, addr) => break size,
+                Ok(_) => (),
+                Err(_) => return Ok(false),
+            }
+
+            // Stray packets shouldn't extend the timeout
+            let remaining = deadline.saturating_duration_since(Instant::now());
+
+            if remaining.is_zero() || socket.set_read_timeout(Some(remaining)).is_err() {
+                return Ok(false);
+            }
+        };
+
+        match &response[..size] {
+            // Open connection reply 1: id, magic, server guid, use security, mtu
+            [0x06, rest @ ..] if rest.len() >= 27 && rest[..16] == RAKNET_MAGIC => return Ok(true),
+            // Incompatible protocol version: id, server protocol version, magic, server guid
+            [0x19, version, rest @ ..] if rest.len() >= 24 && rest[..16] == RAKNET_MAGIC && *version != protocol_version => protocol_version = *version,
+            _ => return Ok(false),
+        }
+    }
+
+    Ok(false)
+}
+
+/// Returns open connection request 1: id 0x05, [`RAKNET_MAGIC`], `protocol_version` and zero padding up to MTU.
+fn build_open_connection_request_1(protocol_version: u8) -> Vec<u8> {
+    let mut request = Vec::with_capacity(OPEN_CONNECTION_REQUEST_1_SIZE);
+
+    request.push(0x05);
+    request.extend_from_slice(&RAKNET_MAGIC);
+    request.push(protocol_version);
+    request.resize(OPEN_CONNECTION_REQUEST_1_SIZE, 0);
+
+    request
+}
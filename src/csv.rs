@@ -0,0 +1,67 @@
+//! Exporting results as CSV.
+
+use std::io::{self, Write};
+
+use crate::ServerIdStringParsed;
+
+/// Writes `servers` as CSV (e.g. to open scan results in a spreadsheet): a header and one row per server.
+/// Columns are edition, motd, version, players, max, gamemode and port (IPv4 one).
+///
+/// Fields with commas, quotes or line breaks are quoted, quotes inside them are doubled.
+///
+/// # Arguments
+///
+/// * `writer` - where to write (e.g. *File* or *Vec<u8>*).
+/// * `servers` - parsed server id strings.
+///
+/// # Panics
+///
+/// Function can return an error only if writing to `writer` failed.
+///
+/// # Example
+///
+/// ```
+/// use mcpe_motd::{parse_server_id_string, write_csv};
+///
+/// let servers = [
+///     parse_server_id_string("MCPE;Hello, \"world\";615;1.20.30;3;10;1;world;Survival;1;19132;19133").unwrap(),
+///     parse_server_id_string("MCPE;Dedicated Server;618;1.20.41;0;10").unwrap(),
+/// ];
+///
+/// let mut csv = Vec::new();
+/// write_csv(&mut csv, &servers).unwrap();
+///
+/// assert_eq!(String::from_utf8(csv).unwrap(), "\
+/// edition,motd,version,players,max,gamemode,port
+/// MCPE,\"Hello, \"\"world\"\"\",1.20.30,3,10,Survival,19132
+/// MCPE,Dedicated Server,1.20.41,0,10,Survival,19132
+/// ");
+/// ```
+pub fn write_csv(mut writer: impl Write, servers: &[ServerIdStringParsed]) -> io::Result<()> {
+    writeln!(writer, "edition,motd,version,players,max,gamemode,port")?;
+
+    for server in servers {
+        writeln!(
+            writer,
+            "{},{},{},{},{},{},{}",
+            escape(server.edition.as_str()),
+            escape(&server.motd),
+            escape(&server.version_name),
+            server.player_count,
+            server.max_player_count,
+            escape(&server.gamemode),
+            server.port_v4,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Quotes `field` if it has characters that are special in CSV.
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,101 @@
+//! Finding out what changed between two pongs.
+
+use crate::{Edition, ServerIdStringParsed};
+
+/// Field that differs between two [`ServerIdStringParsed`], with the old and the new value.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangedField {
+    /// *edition* changed.
+    Edition(Edition, Edition),
+    /// *motd* changed.
+    Motd(String, String),
+    /// *protocol_version* changed.
+    ProtocolVersion(u16, u16),
+    /// *version_name* changed.
+    VersionName(String, String),
+    /// *player_count* changed.
+    PlayerCount(i32, i32),
+    /// *max_player_count* changed.
+    MaxPlayerCount(i32, i32),
+    /// *server_unique_id* changed.
+    ServerUniqueId(String, String),
+    /// *level_name* changed.
+    LevelName(String, String),
+    /// *gamemode* changed.
+    Gamemode(String, String),
+    /// *gamemode_numeric* changed.
+    GamemodeNumeric(u8, u8),
+    /// *port_v4* changed.
+    PortV4(u16, u16),
+    /// *port_v6* changed.
+    PortV6(u16, u16),
+    /// *extra_fields* changed.
+    ExtraFields(Vec<String>, Vec<String>),
+}
+
+impl ServerIdStringParsed {
+    /// Returns every field that differs from `other`, as (`self` value, `other` value), in the order of server id string.
+    /// Empty if both are equal.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcpe_motd::{parse_server_id_string, ChangedField};
+    ///
+    /// let before = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30;3;10").unwrap();
+    /// let after = parse_server_id_string("MCPE;Dedicated Server;618;1.20.40;5;10").unwrap();
+    ///
+    /// assert_eq!(before.diff(&after), [
+    ///     ChangedField::ProtocolVersion(615, 618),
+    ///     ChangedField::VersionName("1.20.30".into(), "1.20.40".into()),
+    ///     ChangedField::PlayerCount(3, 5),
+    /// ]);
+    /// assert!(after.diff(&after).is_empty());
+    /// ```
+    pub fn diff(&self, other: &Self) -> Vec<ChangedField> {
+        let mut changed = Vec::new();
+
+        if self.edition != other.edition {
+            changed.push(ChangedField::Edition(self.edition.clone(), other.edition.clone()));
+        }
+        if self.motd != other.motd {
+            changed.push(ChangedField::Motd(self.motd.clone(), other.motd.clone()));
+        }
+        if self.protocol_version != other.protocol_version {
+            changed.push(ChangedField::ProtocolVersion(self.protocol_version, other.protocol_version));
+        }
+        if self.version_name != other.version_name {
+            changed.push(ChangedField::VersionName(self.version_name.clone(), other.version_name.clone()));
+        }
+        if self.player_count != other.player_count {
+            changed.push(ChangedField::PlayerCount(self.player_count, other.player_count));
+        }
+        if self.max_player_count != other.max_player_count {
+            changed.push(ChangedField::MaxPlayerCount(self.max_player_count, other.max_player_count));
+        }
+        if self.server_unique_id != other.server_unique_id {
+            changed.push(ChangedField::ServerUniqueId(self.server_unique_id.clone(), other.server_unique_id.clone()));
+        }
+        if self.level_name != other.level_name {
+            changed.push(ChangedField::LevelName(self.level_name.clone(), other.level_name.clone()));
+        }
+        if self.gamemode != other.gamemode {
+            changed.push(ChangedField::Gamemode(self.gamemode.clone(), other.gamemode.clone()));
+        }
+        if self.gamemode_numeric != other.gamemode_numeric {
+            changed.push(ChangedField::GamemodeNumeric(self.gamemode_numeric, other.gamemode_numeric));
+        }
+        if self.port_v4 != other.port_v4 {
+            changed.push(ChangedField::PortV4(self.port_v4, other.port_v4));
+        }
+        if self.port_v6 != other.port_v6 {
+            changed.push(ChangedField::PortV6(self.port_v6, other.port_v6));
+        }
+        if self.extra_fields != other.extra_fields {
+            changed.push(ChangedField::ExtraFields(self.extra_fields.clone(), other.extra_fields.clone()));
+        }
+
+        changed
+    }
+}
@@ -0,0 +1,122 @@
+//! Parsing pongs pasted as text (e.g. captures attached to bug reports).
+
+use crate::{parse_unconnected_pong, MotdError, MotdErrorCode, Result, UnconnectedPong};
+
+/// Same as [`parse_unconnected_pong`], but the datagram is given as hex digits (e.g. copied from Wireshark).
+/// Whitespace between the digits is ignored, case doesn't matter.
+///
+/// # Arguments
+///
+/// * `hex` - whole unconnected pong datagram as hex digits.
+///
+/// # Panics
+///
+/// Function can return an error if:
+///  - `hex` has something else than hex digits and whitespace, or an odd number of digits (*MotdErrorCode::InvalidDump*)
+///  - decoded bytes aren't a valid unconnected pong (same errors as [`parse_unconnected_pong`])
+///
+/// # Example
+///
+/// ```
+/// use mcpe_motd::{parse_server_id_string, parse_unconnected_pong_hex, MotdErrorCode, UnconnectedPong};
+///
+/// let pong = UnconnectedPong::new(1000, 0x1234, parse_server_id_string("MCPE;Dedicated Server;615;1.20.30;3;10;13253860892328930865;Bedrock level;Survival;1;19132;19133").unwrap());
+/// let hex: Vec<String> = pong.to_bytes().iter().map(|byte| format!("{:02x}", byte)).collect();
+///
+/// assert_eq!(parse_unconnected_pong_hex(&hex.join(" ")).unwrap(), pong);
+/// assert_eq!(parse_unconnected_pong_hex(&hex.join("\n").to_uppercase()).unwrap(), pong);
+///
+/// let e = parse_unconnected_pong_hex("1c 0").unwrap_err();
+/// assert_eq!(e.code, MotdErrorCode::InvalidDump);
+/// ```
+pub fn parse_unconnected_pong_hex(hex: &str) -> Result<UnconnectedPong> {
+    let digits: Vec<char> = hex.chars().filter(|c| !c.is_whitespace()).collect();
+
+    if digits.len() % 2 != 0 {
+        return Err(MotdError::new(MotdErrorCode::InvalidDump, format!("Hex dump has an odd number of digits ({})", digits.len())));
+    }
+
+    let mut bytes = Vec::with_capacity(digits.len() / 2);
+
+    for pair in digits.chunks(2) {
+        match (pair[0].to_digit(16), pair[1].to_digit(16)) {
+            (Some(high), Some(low)) => bytes.push((high << 4 | low) as u8),
+            _ => { return Err(MotdError::new(MotdErrorCode::InvalidDump, format!("Hex dump has invalid byte {}{}", pair[0], pair[1]))); }
+        }
+    }
+
+    parse_unconnected_pong(&bytes)
+}
+
+/// Same as [`parse_unconnected_pong`], but the datagram is given as base64 (standard alphabet, padding is optional).
+/// Whitespace (e.g. line breaks) is ignored.
+///
+/// # Arguments
+///
+/// * `b64` - whole unconnected pong datagram as base64.
+///
+/// # Panics
+///
+/// Function can return an error if:
+///  - `b64` isn't valid base64 (*MotdErrorCode::InvalidDump*)
+///  - decoded bytes aren't a valid unconnected pong (same errors as [`parse_unconnected_pong`])
+///
+/// # Example
+///
+/// ```
+/// use mcpe_motd::{parse_unconnected_pong_base64, MotdErrorCode};
+///
+/// let pong = parse_unconnected_pong_base64("\
+///     HAAAAAAAAAPoAAAAAAAAEjQA//8A/v7+/v39/f0SNFZ4ACFNQ1BFO0RlZGljYXRlZCBTZXJ2ZXI7\n\
+///     NjE1OzEuMjAuMzA=").unwrap();
+///
+/// assert_eq!(pong.time_since_start, 1000);
+/// assert_eq!(pong.server_id_string_raw, "MCPE;Dedicated Server;615;1.20.30");
+///
+/// let e = parse_unconnected_pong_base64("HA?A").unwrap_err();
+/// assert_eq!(e.code, MotdErrorCode::InvalidDump);
+/// ```
+#[cfg(feature = "base64")]
+pub fn parse_unconnected_pong_base64(b64: &str) -> Result<UnconnectedPong> {
+    let mut bytes = Vec::with_capacity(b64.len() * 3 / 4);
+    let mut acc: u32 = 0;
+    let mut bits = 0;
+    let mut digits = 0;
+    let mut padding = 0;
+
+    for c in b64.chars().filter(|c| !c.is_whitespace()) {
+        let value = match c {
+            'A'..='Z' => c as u32 - 'A' as u32,
+            'a'..='z' => c as u32 - 'a' as u32 + 26,
+            '0'..='9' => c as u32 - '0' as u32 + 52,
+            '+' => 62,
+            '/' => 63,
+            '=' => {
+                padding += 1;
+                continue;
+            }
+            _ => { return Err(MotdError::new(MotdErrorCode::InvalidDump, format!("Base64 dump has invalid character {:?}", c))); }
+        };
+
+        if padding > 0 {
+            return Err(MotdError::new(MotdErrorCode::InvalidDump, String::from("Base64 dump has data after padding")));
+        }
+
+        acc = acc << 6 | value;
+        bits += 6;
+        digits += 1;
+
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+
+    // Single digit of the last group can't hold a whole byte
+    if digits % 4 == 1 || padding > 2 {
+        return Err(MotdError::new(MotdErrorCode::InvalidDump, String::from("Base64 dump has invalid length")));
+    }
+
+    parse_unconnected_pong(&bytes)
+}
@@ -1,12 +1,160 @@
 //! # MCPE MOTD
 //!  A library to fetch some information from MCPE (MCBE actually) over raknet.
+//!
+//! # Features
+//!
+//!  - `base64` - `parse_unconnected_pong_base64` for pongs pasted as base64.
+//!  - `csv` - `write_csv` for exporting parsed server id strings.
+//!  - `logging` - `log` records about sent pings, received packets, filled default fields and errors.
+//!  - `serde` - `Serialize` and `Deserialize` for results and errors.
+//!  - `tokio` - `fetch_unconected_pong_async` for async code.
+//!  - `tos` - `FetchOptions::tos` for setting IP ToS / traffic class of the ping.
 
-use std::net::UdpSocket;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::io::ErrorKind;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// `log::debug!` with `logging` feature, nothing otherwise.
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "logging")]
+        log::debug!($($arg)*);
+        #[cfg(not(feature = "logging"))]
+        if false { let _ = format_args!($($arg)*); }
+    };
+}
+
+/// `log::warn!` with `logging` feature, nothing otherwise.
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "logging")]
+        log::warn!($($arg)*);
+        #[cfg(not(feature = "logging"))]
+        if false { let _ = format_args!($($arg)*); }
+    };
+}
+
+#[cfg(feature = "tokio")]
+mod async_fetch;
+mod borrowed;
+mod connect;
+#[cfg(feature = "csv")]
+mod csv;
+mod diff;
+mod dump;
+mod monitor;
+mod ping;
+mod rate_limit;
+mod retry;
+mod scan;
+mod software;
+mod versions;
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "tokio")]
+pub use async_fetch::fetch_unconected_pong_async;
+pub use borrowed::{parse_server_id_string_ref, ServerIdStringRef};
+pub use connect::check_connectable;
+#[cfg(feature = "csv")]
+pub use csv::write_csv;
+pub use diff::ChangedField;
+#[cfg(feature = "base64")]
+pub use dump::parse_unconnected_pong_base64;
+pub use dump::parse_unconnected_pong_hex;
+pub use monitor::{is_online, monitor, poll, poll_with_socket, status_line, ServerStatus, StatusMonitor, StatusTransition, WindowStats};
+pub use ping::{ping, ping_quality, ping_stats, LatencyStats, PingQuality};
+pub use rate_limit::RateLimiter;
+pub use scan::{discover_lan, discover_lan_iter, discover_lan_iter_on, fetch_fastest, fetch_many, fetch_many_with, group_by_edition, scan_ports, scan_ports_with, Scanner};
+pub use software::ServerSoftware;
+pub use versions::protocol_to_version_name;
+
+/// Port Bedrock servers listen on by default (both IPv4 and IPv6), Education edition servers use the same one.
+/// Parser uses it for missing port fields of the server id string.
+///
+/// # Example
+///
+/// ```
+/// use mcpe_motd::{parse_server_id_string, DEFAULT_PORT};
+///
+/// let parsed = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30").unwrap();
+///
+/// assert_eq!(parsed.port_v4, DEFAULT_PORT);
+/// assert_eq!(parsed.port_v6, DEFAULT_PORT);
+/// ```
+pub const DEFAULT_PORT: u16 = 19132;
+
+/// How long [`fetch_unconected_pong`] waits for the server to respond.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default size of the buffer pongs are received into.
+const DEFAULT_BUFFER_SIZE: usize = 2048;
+
+/// RakNet offline message magic, every unconnected (offline) packet carries it.
+pub const RAKNET_MAGIC: [u8; 16] = [0x00, 0xff, 0xff, 0x00, 0xfe, 0xfe, 0xfe, 0xfe, 0xfd, 0xfd, 0xfd, 0xfd, 0x12, 0x34, 0x56, 0x78];
+
+/// How often a fetch with [`FetchOptions::cancel`] checks the flag while waiting for the response.
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// How many characters of a server id string error messages quote.
+const ERROR_QUOTE_LEN: usize = 64;
+
+/// Time field of the pings sent by default.
+const DEFAULT_PING_TIME: u64 = 0x10;
+
+/// RakNet unconnected ping packet sent to the server.
+const UNCONNECTED_PING: [u8; 33] = build_unconnected_ping(0, DEFAULT_PING_TIME);
+
+/// Returns [RakNet unconnected ping packet](https://wiki.vg/Raknet_Protocol#Unconnected_Ping):
+/// id 0x01, `time` (bytes 1 - 8), [`RAKNET_MAGIC`] (bytes 9 - 24) and `client_guid` (bytes 25 - 32), numbers are big-endian.
+/// Server echoes `time` back in the pong.
+///
+/// # Example
+///
+/// ```
+/// use mcpe_motd::{build_unconnected_ping, RAKNET_MAGIC};
+///
+/// let ping = build_unconnected_ping(0x1234, 16);
+///
+/// assert_eq!(ping[0], 0x01);
+/// assert_eq!(ping[1..9], 16u64.to_be_bytes());
+/// assert_eq!(ping[9..25], RAKNET_MAGIC);
+/// assert_eq!(ping[25..33], 0x1234u64.to_be_bytes());
+/// ```
+pub const fn build_unconnected_ping(client_guid: u64, time: u64) -> [u8; 33] {
+    let time = time.to_be_bytes();
+    let client_guid = client_guid.to_be_bytes();
+
+    let mut ping = [0; 33];
+    ping[0] = 0x01;
+
+    let mut i = 0;
+    while i < 8 {
+        ping[1 + i] = time[i];
+        ping[25 + i] = client_guid[i];
+        i += 1;
+    }
+
+    let mut i = 0;
+    while i < 16 {
+        ping[9 + i] = RAKNET_MAGIC[i];
+        i += 1;
+    }
+
+    ping
+}
 
 /// Enumerates the possible errors you can get.
-#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MotdErrorCode {
-    /// UdpSocket couldn't bind on 0.0.0.0:0 (random port that system will give us).
+    /// UdpSocket couldn't bind on 0.0.0.0:0 or \[::\]:0 (random port that system will give us) or on [`FetchOptions::bind_addr`], or couldn't be configured (e.g. ttl).
     CantBind = 1,
     /// UdpSocket couldn't send raknet packet to the target server.
     CantSendTo = 2,
@@ -24,37 +172,249 @@ pub enum MotdErrorCode {
     CantParsePort4 = 8,
     /// Minecraft won't work with that field if it isn't a valid number.
     CantParsePort6 = 9,
+    /// UdpSocket couldn't receive response from the target server.
+    CantReceive = 10,
+    /// Target server didn't respond in time, or the ping couldn't be sent in time (see [`FetchOptions::send_timeout`]).
+    Timeout = 11,
+    /// Response is shorter than unconnected pong header (including empty datagrams).
+    PacketTooShort = 12,
+    /// Response doesn't have RakNet offline message magic, so it's probably not a RakNet packet at all.
+    InvalidMagic = 13,
+    /// Response isn't an unconnected pong (0x1c).
+    UnexpectedPacketId = 14,
+    /// Response filled the whole receive buffer, so it was probably cut off (see [`FetchOptions::buffer_size`]).
+    PacketTruncated = 15,
+    /// Server id string isn't valid UTF-8 (only with [`FetchOptions::strict_utf8`]).
+    InvalidUtf8 = 16,
+    /// Server id string length in the header is larger than the rest of the packet (e.g. padded by a proxy).
+    LengthMismatch = 17,
+    /// Fetch was cancelled with the flag passed to [`FetchOptions::cancel`].
+    Cancelled = 18,
+    /// Optional field is missing or empty and [`FetchOptions::strict`] is enabled, message names the field.
+    MissingField = 19,
+    /// Address couldn't be resolved or didn't resolve to anything (e.g. a typo in the hostname).
+    CantResolveAddress = 20,
+    /// Hex or base64 dump of a pong couldn't be decoded into bytes.
+    InvalidDump = 21,
+}
+
+impl MotdErrorCode {
+    /// Stable number of the code (e.g. 1 for *CantBind*), numbers never change between versions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcpe_motd::MotdErrorCode;
+    ///
+    /// assert_eq!(MotdErrorCode::CantBind.code_number(), 1);
+    /// assert_eq!(MotdErrorCode::Timeout.code_number(), 11);
+    /// ```
+    pub fn code_number(&self) -> u32 {
+        *self as u32
+    }
 }
 
 /// Custom error type.
-#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MotdError {
     /// Error code.
     pub code: MotdErrorCode,
     /// More detailed info about an error.
     pub message: String,
+    /// Server id string as it was received, if the error happened while parsing it (e.g. *MotdErrorCode::CantParseProtocolVersion*).
+    /// Invalid UTF-8 is replaced with `�` here, even with [`FetchOptions::strict_utf8`].
+    pub raw: Option<String>,
+    /// The whole received packet, only for parse errors with [`FetchOptions::return_raw_on_error`]
+    /// (e.g. to dump it as hex and parse it later with [`parse_unconnected_pong_hex`]).
+    pub raw_bytes: Option<Vec<u8>>,
+}
+
+impl MotdError {
+    /// Creates error with `code` and `message`, without *raw* and *raw_bytes*.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcpe_motd::{MotdError, MotdErrorCode};
+    ///
+    /// let e = MotdError::new(MotdErrorCode::Timeout, "Server didn't respond");
+    ///
+    /// assert_eq!(e.to_string(), "Timeout: Server didn't respond");
+    /// assert_eq!(e.raw, None);
+    /// ```
+    pub fn new(code: MotdErrorCode, message: impl Into<String>) -> Self {
+        MotdError { code, message: message.into(), raw: None, raw_bytes: None }
+    }
+
+    /// Stable number of the error code, see [`MotdErrorCode::code_number`].
+    pub fn code_number(&self) -> u32 {
+        self.code.code_number()
+    }
+}
+
+impl fmt::Display for MotdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {}", self.code, self.message)
+    }
+}
+
+impl Error for MotdError {}
+
+/// Turns io errors into *MotdError*, keeping their message.
+/// Timeouts become *MotdErrorCode::Timeout*, address errors *MotdErrorCode::CantBind* and everything else *MotdErrorCode::CantReceive*.
+impl From<std::io::Error> for MotdError {
+    fn from(e: std::io::Error) -> Self {
+        let code = match e.kind() {
+            ErrorKind::WouldBlock | ErrorKind::TimedOut => MotdErrorCode::Timeout,
+            ErrorKind::AddrInUse | ErrorKind::AddrNotAvailable => MotdErrorCode::CantBind,
+            _ => MotdErrorCode::CantReceive,
+        };
+
+        MotdError::new(code, e.to_string())
+    }
+}
+
+/// Result with *MotdError*, returned by every fallible function of the crate.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::net::UdpSocket;
+/// use mcpe_motd::{parse_unconnected_pong, Result, UnconnectedPong};
+///
+/// fn receive_pong(socket: &UdpSocket) -> Result<UnconnectedPong> {
+///     let mut response = [0; 2048];
+///     // io::Error is converted into MotdError
+///     let (size, _) = socket.recv_from(&mut response)?;
+///
+///     parse_unconnected_pong(&response[..size])
+/// }
+/// ```
+pub type Result<T, E = MotdError> = std::result::Result<T, E>;
+
+/// Minecraft edition from the first field of server id string.
+/// With `serde` feature it's (de)serialized as the raw string ("MCPE", "MCEE", ...).
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(from = "String", into = "String"))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Edition {
+    /// Bedrock edition ("MCPE").
+    Mcpe,
+    /// Education edition ("MCEE").
+    Mcee,
+    /// Anything else, exactly as server sent it.
+    Other(String),
+}
+
+impl Edition {
+    /// Edition as it's written in server id string.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Edition::Mcpe => "MCPE",
+            Edition::Mcee => "MCEE",
+            Edition::Other(raw) => raw,
+        }
+    }
+}
+
+impl From<&str> for Edition {
+    fn from(raw: &str) -> Self {
+        match raw {
+            "MCPE" => Edition::Mcpe,
+            "MCEE" => Edition::Mcee,
+            _ => Edition::Other(raw.to_string()),
+        }
+    }
+}
+
+impl From<String> for Edition {
+    fn from(raw: String) -> Self {
+        match raw.as_str() {
+            "MCPE" => Edition::Mcpe,
+            "MCEE" => Edition::Mcee,
+            _ => Edition::Other(raw),
+        }
+    }
+}
+
+impl From<Edition> for String {
+    fn from(edition: Edition) -> Self {
+        match edition {
+            Edition::Other(raw) => raw,
+            _ => edition.as_str().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Edition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Default gamemode from the numeric gamemode field of server id string.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Gamemode {
+    /// 0.
+    Survival,
+    /// 1.
+    Creative,
+    /// 2.
+    Adventure,
+    /// 3.
+    Spectator,
+    /// Any other number.
+    Unknown(u8),
+}
+
+impl From<u8> for Gamemode {
+    fn from(numeric: u8) -> Self {
+        match numeric {
+            0 => Gamemode::Survival,
+            1 => Gamemode::Creative,
+            2 => Gamemode::Adventure,
+            3 => Gamemode::Spectator,
+            _ => Gamemode::Unknown(numeric),
+        }
+    }
+}
+
+impl Gamemode {
+    /// Gamemode named by the *gamemode* string field (case-insensitive), `None` for unknown names.
+    fn from_name(name: &str) -> Option<Self> {
+        const NAMES: [(&str, Gamemode); 4] = [
+            ("Survival", Gamemode::Survival),
+            ("Creative", Gamemode::Creative),
+            ("Adventure", Gamemode::Adventure),
+            ("Spectator", Gamemode::Spectator),
+        ];
+
+        NAMES.into_iter().find(|(known, _)| name.eq_ignore_ascii_case(known)).map(|(_, gamemode)| gamemode)
+    }
 }
 
 /// Parsed [server id string](https://wiki.vg/Raknet_Protocol#Unconnected_Pong).
 /// **Be careful, if server id string is invalid (e.g. has fewer fields), lib will (at least try to) add default ones.**
 /// However, there is *UnconnectedPong* struct with *server_id_string_parsed_ok* field.
-#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ServerIdStringParsed {
     /// Server minecraft edition (MCPE or MCEE).
-    pub edition: String,
+    pub edition: Edition,
     /// Text that is displayed in the server tab.
     pub motd: String,
-    /// Minecraft protocol version (e.g. 615).
-    pub protocol_version: i16,
+    /// Minecraft protocol version (e.g. 615), never negative.
+    pub protocol_version: u16,
     /// Minecraft version name (e.g. 1.20.30).
     pub version_name: String,
     /// How many players is playing on the server.
     pub player_count: i32,
     /// How many players can be playing on the server at the same time.
     pub max_player_count: i32,
-    /// Some unique id.
+    /// Some unique id (usually a number, see *server_unique_id_numeric*).
     pub server_unique_id: String,
-    /// Map name (display in esc menu at the right top).
+    /// Map name (display in esc menu at the right top), server list shows it as the second motd line.
     pub level_name: String,
     /// Default gamemode.
     pub gamemode: String,
@@ -64,250 +424,2816 @@ pub struct ServerIdStringParsed {
     pub port_v4: u16,
     /// Port used for IPv6 communication.
     pub port_v6: u16,
+    /// Fields after *port_v6*: the ones newer Bedrock builds send (see *nintendo_limited*) or nonstandard ones (e.g. added by Nukkit or PocketMine plugins),
+    /// empty for older server id strings.
+    pub extra_fields: Vec<String>,
+    /// How many fields server id string actually had (extra fields included).
+    pub field_count: usize,
+    /// Which optional fields were sent and parsed, rather than filled with defaults, see *present_fields*.
+    pub sent_fields: FieldPresence,
 }
 
-/// Parsed [RakNet unconnected pong packet](https://wiki.vg/Raknet_Protocol#Unconnected_Pong).
-/// Has more information than *ServerIdStringParsed*.
-/// Unlike *ServerIdStringParsed*, using *UnconnectedPong* you can check if server id string was parsed correctly (without adding default ones).
-#[derive(Debug)]
-pub struct UnconnectedPong {
-    /// Packet id (0x1c).
-    pub id: u8,
-    /// Time since server start in ms.
-    pub time_since_start: i64,
-    /// Server guid.
-    pub server_guid: i64,
-    /// Whoops magic...
-    pub magic: [u8; 16],
-    /// Length of server id string.
-    pub server_id_string_len: i16,
-    /// Raw server id string.
-    pub server_id_string_raw: String,
-    /// Whether server id string was parsed correctly.
-    pub server_id_string_parsed_ok: bool,
-    /// Parsed server id string.
-    pub server_id_string_parsed: ServerIdStringParsed,
+/// Why *server_id_string_parsed_ok* of a pong is `false`, see *UnconnectedPong::warnings*.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseWarning {
+    /// *player_count* is missing or empty, -1 is used.
+    MissingPlayerCount,
+    /// *max_player_count* is missing or empty, -1 is used.
+    MissingMaxPlayerCount,
+    /// *server_unique_id* is missing, empty string is used.
+    MissingServerUniqueId,
+    /// *level_name* is missing, empty string is used.
+    MissingLevelName,
+    /// *gamemode* is missing, "Survival" is used.
+    MissingGamemode,
+    /// *gamemode_numeric* is missing or empty, 0 is used.
+    MissingGamemodeNumeric,
+    /// *port_v4* is missing or empty, [`DEFAULT_PORT`] is used.
+    MissingPortV4,
+    /// *port_v6* is missing or empty, [`DEFAULT_PORT`] is used.
+    MissingPortV6,
+    /// *gamemode_numeric* is outside 0 - 3.
+    UnknownGamemode(u8),
+    /// Reported port is 0, only with [`FetchOptions::validate_ports`].
+    InvalidPort,
+    /// *player_count* isn't a number, -1 is used (only with [`FetchOptions::lenient`]).
+    InvalidPlayerCount,
+    /// *max_player_count* isn't a number, -1 is used (only with [`FetchOptions::lenient`]).
+    InvalidMaxPlayerCount,
+    /// *gamemode_numeric* isn't a number, 0 is used (only with [`FetchOptions::lenient`]).
+    InvalidGamemodeNumeric,
+    /// *port_v4* isn't a number, [`DEFAULT_PORT`] is used (only with [`FetchOptions::lenient`]).
+    InvalidPortV4,
+    /// *port_v6* isn't a number, [`DEFAULT_PORT`] is used (only with [`FetchOptions::lenient`]).
+    InvalidPortV6,
+    /// *edition* is neither MCPE nor MCEE (e.g. garbage from a corrupt packet).
+    UnknownEdition,
+    /// Server id string isn't valid UTF-8, invalid bytes were replaced with `�` (without [`FetchOptions::strict_utf8`]).
+    InvalidUtf8,
+    /// *version_name* isn't the release known to use *protocol_version* (see [`protocol_to_version_name`]),
+    /// usually a config that wasn't updated. Only checked for protocols in the table.
+    VersionMismatch,
+    /// Server id string ended in the middle of a character, which was dropped.
+    TruncatedString,
+    /// Server id string built back from the parsed fields isn't the one server sent, only with [`FetchOptions::verify_roundtrip`].
+    RoundtripMismatch,
 }
 
-/// Returns parsed [RakNet unconnected pong packet](https://wiki.vg/Raknet_Protocol#Unconnected_Pong) or error explaining why packet wasn't parsed.
-///
-/// # Arguments
-///
-/// * `addr` - address of the target server.
-///
-/// # Panics
-///
-/// Function can return an error if:
-///  - couldn't send packet to the target server
-///  - couldn't parse response (e.g. invalid unconnected pong packet)
-///
-/// However, it will try to replace invalid data with default ones (e.g. empty port field in server id string will be replaced with 19132) until minecraft can process that data.
-/// Obviously, minecraft won't work with empty server id string or *String* instead of *version_protocol field*.
+/// Which optional fields of server id string were actually sent, returned by [`ServerIdStringParsed::present_fields`].
+/// Fields that weren't sent (or were empty or invalid numbers) have default values.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct FieldPresence {
+    /// *player_count* was sent.
+    pub player_count: bool,
+    /// *max_player_count* was sent.
+    pub max_player_count: bool,
+    /// *server_unique_id* was sent.
+    pub server_unique_id: bool,
+    /// *level_name* was sent.
+    pub level_name: bool,
+    /// *gamemode* was sent.
+    pub gamemode: bool,
+    /// *gamemode_numeric* was sent.
+    pub gamemode_numeric: bool,
+    /// *port_v4* was sent.
+    pub port_v4: bool,
+    /// *port_v6* was sent.
+    pub port_v6: bool,
+}
+
+/// Human-readable multi-line summary: motd (without formatting codes), edition, version, players, gamemode and ports.
+/// Player counts the server didn't send are shown as `?`.
+impl fmt::Display for ServerIdStringParsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Motd: {}", self.motd_plain())?;
+        writeln!(f, "Edition: {}", self.edition)?;
+        writeln!(f, "Version: {} (protocol {})", self.version_name, self.protocol_version)?;
+        writeln!(f, "Players: {}", self.players_display())?;
+        writeln!(f, "Gamemode: {} ({})", self.gamemode, self.gamemode_numeric)?;
+        write!(f, "Ports: {} (IPv4), {} (IPv6)", self.port_v4, self.port_v6)
+    }
+}
+
+/// The same default values parser adds for missing optional fields:
+/// -1 player counts, empty server unique id and level name, "Survival" (0) gamemode and [`DEFAULT_PORT`] ports.
+/// Required fields (edition, motd, protocol version and version name) are empty / 0.
+impl Default for ServerIdStringParsed {
+    fn default() -> Self {
+        ServerIdStringParsed {
+            edition: Edition::Other(String::new()),
+            motd: String::new(),
+            protocol_version: 0,
+            version_name: String::new(),
+            player_count: -1,
+            max_player_count: -1,
+            server_unique_id: String::new(),
+            level_name: String::new(),
+            gamemode: String::from("Survival"),
+            gamemode_numeric: 0,
+            port_v4: DEFAULT_PORT,
+            port_v6: DEFAULT_PORT,
+            extra_fields: Vec::new(),
+            field_count: 0,
+            sent_fields: FieldPresence::default(),
+        }
+    }
+}
+
+/// Same as [`parse_server_id_string`], for server id strings obtained elsewhere.
 ///
 /// # Example
 ///
 /// ```
-/// use::mcpe_motd::fetch_unconected_pong;
+/// use mcpe_motd::{MotdError, ServerIdStringParsed};
 ///
-/// let pong = match fetch_unconected_pong("127.0.0.1:19132") {
-///     Ok(pong) => pong,
-///     Err(e) => panic!(e)
-/// };
+/// let parsed: ServerIdStringParsed = "MCPE;Dedicated Server;615;1.20.30;3;10".try_into()?;
+/// assert_eq!(parsed.player_count, 3);
 ///
-/// println!("Server id string was correctly parsed (true / false): {}.", pong.server_id_string_parsed_ok);
-/// println!("Raw server id string: {}.", pong.server_id_string_raw);
-/// println!("Server guid: {}.", pong.server_guid);
+/// assert!(ServerIdStringParsed::try_from(String::from("MCPE;Dedicated Server")).is_err());
+/// # Ok::<(), MotdError>(())
 /// ```
-pub fn fetch_unconected_pong(addr: &str) -> Result<UnconnectedPong, MotdError> {
-    let socket = match UdpSocket::bind("0.0.0.0:0") {
-        Ok(sock) => sock,
-        Err(_) => { return Err(MotdError { code: MotdErrorCode::CantSendTo, message: String::from("Couldn't bind to 0.0.0.0:0") }); }
-    };
+impl TryFrom<&str> for ServerIdStringParsed {
+    type Error = MotdError;
+
+    fn try_from(raw: &str) -> Result<Self, Self::Error> {
+        parse_server_id_string(raw)
+    }
+}
 
-    let buf: &[u8] = &[/*ID*/ 0x01, /*Time*/ 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10, /*MAGIC*/ 0x00, 0xff, 0xff, 0x00, 0xfe, 0xfe, 0xfe, 0xfe, 0xfd, 0xfd, 0xfd, 0xfd, 0x12, 0x34, 0x56, 0x78, /*Client GUID*/ 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+/// Same as [`parse_server_id_string`], for server id strings obtained elsewhere.
+impl TryFrom<String> for ServerIdStringParsed {
+    type Error = MotdError;
 
-    match socket.send_to(buf, addr) {
-        Ok(_) => (),
-        Err(_) => { return Err(MotdError { code: MotdErrorCode::CantSendTo, message: String::from("Couldn't send to ... (here should be ip)") }); }
+    fn try_from(raw: String) -> Result<Self, Self::Error> {
+        parse_server_id_string(&raw)
     }
+}
 
-    let mut response: [u8; 1024] = [0; 1024];
-    let (size, _src) = socket.recv_from(&mut response).expect("ddd");
-    let response = &mut response[..size];
+impl ServerIdStringParsed {
+    /// Joins the fields back into `;`-delimited server id string in the order Bedrock sends them
+    /// (edition, motd, protocol_version, version_name, player_count, max_player_count, server_unique_id, level_name, gamemode, gamemode_numeric, port_v4, port_v6),
+    /// followed by *extra_fields*.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcpe_motd::parse_server_id_string;
+    ///
+    /// let raw = "MCPE;Dedicated Server;615;1.20.30;3;10;13253860892328930865;Bedrock level;Survival;1;19132;19133";
+    /// let parsed = parse_server_id_string(raw).unwrap();
+    ///
+    /// assert_eq!(parsed.to_server_id_string(), raw);
+    /// assert_eq!(parse_server_id_string(&parsed.to_server_id_string()).unwrap(), parsed);
+    /// ```
+    pub fn to_server_id_string(&self) -> String {
+        let mut fields: Vec<String> = self.named_fields().into_iter().map(|(_, value)| value).collect();
+        fields.extend(self.extra_fields.iter().cloned());
 
-    // Packet id (0x1c) - 1 byte
-    let id = response[0];
+        fields.join(";")
+    }
 
-    // Time since start in ms - 8 bytes
-    let time_since_start: i64 = (response[8] as i64) |
-        (response[7] as i64) << 8 |
-        (response[6] as i64) << 16 |
-        (response[5] as i64) << 24 |
-        (response[4] as i64) << 32 |
-        (response[3] as i64) << 40 |
-        (response[2] as i64) << 48 |
-        (response[1] as i64) << 56;
+    /// The 12 standard fields as strings keyed by their names (the same as of the struct fields:
+    /// "edition", "motd", "protocol_version", "version_name", "player_count", "max_player_count", "server_unique_id",
+    /// "level_name", "gamemode", "gamemode_numeric", "port_v4" and "port_v6"), e.g. for structured logging or metric labels.
+    /// Key names never change. *extra_fields* aren't included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcpe_motd::parse_server_id_string;
+    ///
+    /// let map = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30;3;10").unwrap().as_map();
+    ///
+    /// assert_eq!(map.len(), 12);
+    /// assert_eq!(map["edition"], "MCPE");
+    /// assert_eq!(map["player_count"], "3");
+    /// assert_eq!(map["port_v4"], "19132");
+    /// ```
+    pub fn as_map(&self) -> HashMap<String, String> {
+        self.named_fields().into_iter().map(|(name, value)| (name.to_string(), value)).collect()
+    }
 
-    // Server GUID - 8 bytes
-    let server_guid: i64 = (response[16] as i64) |
-        (response[15] as i64) << 8 |
-        (response[14] as i64) << 16 |
-        (response[13] as i64) << 24 |
-        (response[12] as i64) << 32 |
-        (response[11] as i64) << 40 |
-        (response[10] as i64) << 48 |
-        (response[9] as i64) << 56;
+    /// Short hash of the fields identifying the server, for noticing the same server among scans without comparing whole structs.
+    ///
+    /// Only *edition*, *version_name*, *server_unique_id*, *port_v4* and *port_v6* (in this order) are hashed,
+    /// so player counts, motd, level name and gamemode may change without changing the fingerprint.
+    /// It's 64-bit FNV-1a over the fields separated by `;` (ports as decimal numbers), the same on every platform and Rust version.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcpe_motd::parse_server_id_string;
+    ///
+    /// let before = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30;3;10;13253860892328930865;world;Survival;1;19132;19133").unwrap();
+    /// let after = parse_server_id_string("MCPE;New motd;615;1.20.30;4;10;13253860892328930865;world;Creative;1;19132;19133").unwrap();
+    /// let updated = parse_server_id_string("MCPE;New motd;618;1.20.40;4;10;13253860892328930865;world;Creative;1;19132;19133").unwrap();
+    ///
+    /// assert_eq!(before.fingerprint(), after.fingerprint());
+    /// assert_ne!(before.fingerprint(), updated.fingerprint());
+    ///
+    /// // Stays the same across versions of the crate, so it can be stored
+    /// assert_eq!(before.fingerprint(), 0xf6143b0a6de16a18);
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        let stable = format!("{};{};{};{};{}", self.edition, self.version_name, self.server_unique_id, self.port_v4, self.port_v6);
 
-    // Magic - 16 bytes
-    const MAGIC: [u8; 16] = [0x00, 0xff, 0xff, 0x00, 0xfe, 0xfe, 0xfe, 0xfe, 0xfd, 0xfd, 0xfd, 0xfd, 0x12, 0x34, 0x56, 0x78];
+        stable.bytes().fold(0xcbf29ce484222325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
+    }
 
-    // Server id string length - 2 bytes
-    let server_id_string_len = (response[34] as i16) |
-        (response[33] as i16) << 8;
+    /// The 12 standard fields in the order Bedrock sends them, with their names.
+    fn named_fields(&self) -> [(&'static str, String); 12] {
+        [
+            ("edition", self.edition.to_string()),
+            ("motd", self.motd.clone()),
+            ("protocol_version", self.protocol_version.to_string()),
+            ("version_name", self.version_name.clone()),
+            ("player_count", self.player_count.to_string()),
+            ("max_player_count", self.max_player_count.to_string()),
+            ("server_unique_id", self.server_unique_id.clone()),
+            ("level_name", self.level_name.clone()),
+            ("gamemode", self.gamemode.clone()),
+            ("gamemode_numeric", self.gamemode_numeric.to_string()),
+            ("port_v4", self.port_v4.to_string()),
+            ("port_v6", self.port_v6.to_string()),
+        ]
+    }
 
-    // Server id string - <server_id_string_len> bytes
-    let server_id_string = String::from_utf8_lossy(&response[35..35 + server_id_string_len as usize]).to_string();
+    /// Both lines shown in the server list: *motd* (raw field 1) and the sub-motd, which Bedrock sends as *level_name* (raw field 7).
+    /// Some servers don't send the sub-motd and put both lines into *motd* separated by a newline instead,
+    /// then *motd* is split on the first newline (see [`ServerIdStringParsed::motd_has_embedded_second_line`]).
+    /// Second line is empty if server didn't send it either way.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcpe_motd::parse_server_id_string;
+    ///
+    /// let parsed = parse_server_id_string("MCPE;Welcome!;615;1.20.30;3;10;13253860892328930865;Survival games;Survival;1;19132;19133").unwrap();
+    /// assert_eq!(parsed.motd_lines(), ("Welcome!", "Survival games"));
+    ///
+    /// let parsed = parse_server_id_string("MCPE;Welcome!\r\nSurvival games;615;1.20.30;3;10").unwrap();
+    /// assert_eq!(parsed.motd_lines(), ("Welcome!", "Survival games"));
+    /// ```
+    pub fn motd_lines(&self) -> (&str, &str) {
+        if self.motd_has_embedded_second_line() {
+            if let Some((first, second)) = self.motd.split_once('\n') {
+                return (first.strip_suffix('\r').unwrap_or(first), second);
+            }
+        }
 
-    let split_server_id_string: &Vec<String> = &server_id_string.split(";")
-        .filter(|s| *s != "")
-        .map(|s| s.to_string())
-        .collect();
+        (&self.motd, &self.level_name)
+    }
 
-    let split_server_id_string_size = split_server_id_string.len();
+    /// Whether *motd* carries the second line itself: it has a newline and there is no sub-motd (empty *level_name*).
+    ///
+    /// `§r` isn't treated as a line separator, servers use it to reset formatting in the middle of a line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcpe_motd::parse_server_id_string;
+    ///
+    /// let parsed = parse_server_id_string("MCPE;Welcome!\nSurvival games;615;1.20.30;3;10").unwrap();
+    /// assert!(parsed.motd_has_embedded_second_line());
+    ///
+    /// let parsed = parse_server_id_string("MCPE;Welcome!;615;1.20.30;3;10;1;Survival games").unwrap();
+    /// assert!(!parsed.motd_has_embedded_second_line());
+    /// ```
+    pub fn motd_has_embedded_second_line(&self) -> bool {
+        self.level_name.is_empty() && self.motd.contains('\n')
+    }
 
-    if split_server_id_string_size < 4 {
-        return Err(MotdError { code: MotdErrorCode::ServerIdStringTooSmall, message: String::from("Server id string has less than 4 required fields") });
+    /// Port clients should connect on: *port_v6* when connecting over IPv6 (e.g. `pong.source_addr.is_some_and(|addr| addr.is_ipv6())`), *port_v4* otherwise.
+    /// Most Bedrock servers report the same port for both (often 19132), but not all of them do.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcpe_motd::parse_server_id_string;
+    ///
+    /// let parsed = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30;3;10;13253860892328930865;Bedrock level;Survival;1;19132;19133").unwrap();
+    ///
+    /// assert_eq!(parsed.port_for(false), 19132);
+    /// assert_eq!(parsed.port_for(true), 19133);
+    /// ```
+    pub fn port_for(&self, is_ipv6: bool) -> u16 {
+        if is_ipv6 { self.port_v6 } else { self.port_v4 }
     }
 
-    let mut server_id_string_parsed_ok = true;
+    /// *player_count* and *max_player_count* together.
+    pub fn players(&self) -> (i32, i32) {
+        (self.player_count, self.max_player_count)
+    }
 
-    let server_id_string_parsed = ServerIdStringParsed {
-        edition: split_server_id_string[0].to_string(),
+    /// Players as the server list shows them (e.g. "3/20"), counts the server didn't send (-1) are shown as `?`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcpe_motd::parse_server_id_string;
+    ///
+    /// let parsed = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30;3;20").unwrap();
+    /// assert_eq!(parsed.players(), (3, 20));
+    /// assert_eq!(parsed.players_display(), "3/20");
+    ///
+    /// let parsed = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30").unwrap();
+    /// assert_eq!(parsed.players_display(), "?/?");
+    /// ```
+    pub fn players_display(&self) -> String {
+        let count = |count: i32| if count < 0 { String::from("?") } else { count.to_string() };
 
-        motd: split_server_id_string[1].to_string(),
+        format!("{}/{}", count(self.player_count), count(self.max_player_count))
+    }
 
-        protocol_version: match split_server_id_string[2].parse() {
-            Ok(v) => v,
-            Err(_) => {
-                return Err(MotdError { code: MotdErrorCode::CantParseProtocolVersion, message: String::from("Couldn't parse protocol_version field from server id string") });
-            }
-        },
+    /// How full the server is (`player_count / max_player_count`, e.g. 0.5 for 5 / 10).
+    /// Returns `None` if *max_player_count* isn't positive (e.g. the -1 default), so there is no division by zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcpe_motd::parse_server_id_string;
+    ///
+    /// let parsed = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30;5;10").unwrap();
+    /// assert_eq!(parsed.fill_ratio(), Some(0.5));
+    /// assert!(!parsed.is_full());
+    ///
+    /// let parsed = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30").unwrap();
+    /// assert_eq!(parsed.fill_ratio(), None);
+    /// assert!(!parsed.is_full());
+    /// ```
+    pub fn fill_ratio(&self) -> Option<f32> {
+        if self.max_player_count <= 0 {
+            return None;
+        }
 
-        version_name: split_server_id_string[3].to_string(),
+        Some(self.player_count.max(0) as f32 / self.max_player_count as f32)
+    }
 
-        player_count: if split_server_id_string_size >= 5 {
-            match split_server_id_string[4].parse() {
-                Ok(v) => v,
-                Err(_) => {
-                    return Err(MotdError { code: MotdErrorCode::CantParsePlayerCount, message: String::from("Couldn't parse player_count field from server id string") });
-                }
-            }
-        } else {
-            server_id_string_parsed_ok = false;
-            -1
-        },
+    /// Whether there is no room for more players (never `true` if *max_player_count* isn't positive).
+    pub fn is_full(&self) -> bool {
+        self.max_player_count > 0 && self.player_count >= self.max_player_count
+    }
 
-        max_player_count: if split_server_id_string_size >= 6 {
-            match split_server_id_string[5].parse() {
-                Ok(v) => v,
-                Err(_) => {
-                    return Err(MotdError { code: MotdErrorCode::CantParsePlayerMaxCount, message: String::from("Couldn't parse max_player_count field from server id string") });
-                }
-            }
-        } else {
-            server_id_string_parsed_ok = false;
-            -1
-        },
+    /// *server_unique_id* as a number (`None` if it isn't one, some nonstandard servers put text there).
+    /// Servers usually keep it across restarts, so it can tell servers apart even if their address changed.
+    pub fn server_unique_id_numeric(&self) -> Option<u64> {
+        self.server_unique_id.parse().ok()
+    }
 
-        server_unique_id: if split_server_id_string_size >= 7 { split_server_id_string[6].to_string() } else { "".to_string() },
+    /// Whether both reported ports are usable (not 0, which almost always means server didn't fill the field properly).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcpe_motd::parse_server_id_string;
+    ///
+    /// let parsed = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30;3;10;13253860892328930865;Bedrock level;Survival;1;19132;0").unwrap();
+    ///
+    /// assert!(!parsed.ports_valid());
+    /// ```
+    pub fn ports_valid(&self) -> bool {
+        self.port_v4 != 0 && self.port_v6 != 0
+    }
 
-        level_name: if split_server_id_string_size >= 8 { split_server_id_string[7].to_string() } else { "".to_string() },
+    /// Which optional fields (5th - 12th) were in the raw server id string, rather than filled with defaults.
+    /// Empty numbers (and invalid ones replaced with [`FetchOptions::lenient`]) count as missing, empty strings are present.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcpe_motd::parse_server_id_string;
+    ///
+    /// let parsed = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30;;10;13253860892328930865;Bedrock level").unwrap();
+    /// let present = parsed.present_fields();
+    ///
+    /// assert!(!present.player_count);
+    /// assert!(present.max_player_count);
+    /// assert!(present.level_name);
+    /// assert!(!present.gamemode);
+    /// assert!(!present.port_v4);
+    /// ```
+    pub fn present_fields(&self) -> FieldPresence {
+        self.sent_fields
+    }
 
-        gamemode: if split_server_id_string_size >= 9 { split_server_id_string[8].to_string() } else { "Survival".to_string() },
+    /// *player_count*, or `None` if the server didn't send it (or sent it empty) and -1 was filled in.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcpe_motd::parse_server_id_string;
+    ///
+    /// let parsed = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30;0;10").unwrap();
+    ///
+    /// assert_eq!(parsed.player_count_opt(), Some(0));
+    /// assert_eq!(parsed.max_player_count_opt(), Some(10));
+    /// assert_eq!(parsed.gamemode_numeric_opt(), None);
+    /// assert_eq!(parsed.port_v4_opt(), None);
+    /// assert_eq!(parsed.port_v4, 19132);
+    /// ```
+    ///
+    /// Empty fields are defaulted as well, so they are `None` too:
+    ///
+    /// ```
+    /// use mcpe_motd::parse_server_id_string;
+    ///
+    /// let parsed = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30;;10;13253860892328930865;Bedrock level;Survival;;19132;19133").unwrap();
+    ///
+    /// assert_eq!(parsed.player_count_opt(), None);
+    /// assert_eq!(parsed.player_count, -1);
+    /// assert_eq!(parsed.gamemode_numeric_opt(), None);
+    /// assert_eq!(parsed.port_v4_opt(), Some(19132));
+    /// ```
+    pub fn player_count_opt(&self) -> Option<i32> {
+        self.present_fields().player_count.then_some(self.player_count)
+    }
 
-        gamemode_numeric: if split_server_id_string_size >= 10 {
-            match split_server_id_string[9].parse() {
-                Ok(v) => v,
-                Err(_) => {
-                    return Err(MotdError { code: MotdErrorCode::CantParseGameModeNum, message: String::from("Couldn't parse gamemode_numeric field from server id string") });
-                }
-            }
-        } else {
-            server_id_string_parsed_ok = false;
-            0
-        },
+    /// *max_player_count*, or `None` if the server didn't send it, see [`ServerIdStringParsed::player_count_opt`].
+    pub fn max_player_count_opt(&self) -> Option<i32> {
+        self.present_fields().max_player_count.then_some(self.max_player_count)
+    }
 
-        port_v4: if split_server_id_string_size >= 11 {
-            match split_server_id_string[10].parse() {
-                Ok(v) => v,
-                Err(_) => {
-                    return Err(MotdError { code: MotdErrorCode::CantParsePort4, message: String::from("Couldn't parse port_v4 field from server id string") });
-                }
+    /// *gamemode_numeric*, or `None` if the server didn't send it, see [`ServerIdStringParsed::player_count_opt`].
+    pub fn gamemode_numeric_opt(&self) -> Option<u8> {
+        self.present_fields().gamemode_numeric.then_some(self.gamemode_numeric)
+    }
+
+    /// *port_v4*, or `None` if the server didn't send it, see [`ServerIdStringParsed::player_count_opt`].
+    pub fn port_v4_opt(&self) -> Option<u16> {
+        self.present_fields().port_v4.then_some(self.port_v4)
+    }
+
+    /// *port_v6*, or `None` if the server didn't send it, see [`ServerIdStringParsed::player_count_opt`].
+    pub fn port_v6_opt(&self) -> Option<u16> {
+        self.present_fields().port_v6.then_some(self.port_v6)
+    }
+
+    /// "Nintendo limited" flag newer Bedrock builds send as field 12 (the first of *extra_fields*),
+    /// `None` if it's missing or isn't `0` / `1` (e.g. a plugin put something else there).
+    ///
+    /// Known trailing fields:
+    ///  - 12 - [`ServerIdStringParsed::nintendo_limited`]
+    ///  - 13 - [`ServerIdStringParsed::editor_mode`]
+    ///
+    /// Both stay in *extra_fields* as well.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcpe_motd::parse_server_id_string;
+    ///
+    /// let parsed = parse_server_id_string("MCPE;Dedicated Server;766;1.21.50;0;10;1;world;Survival;1;19132;19133;0;1;").unwrap();
+    ///
+    /// assert_eq!(parsed.nintendo_limited(), Some(false));
+    /// assert_eq!(parsed.editor_mode(), Some(true));
+    /// assert_eq!(parsed.extra_fields, ["0", "1"]);
+    ///
+    /// let parsed = parse_server_id_string("MCPE;Dedicated Server;766;1.21.50;0;10;1;world;Survival;1;19132;19133;plugin").unwrap();
+    /// assert_eq!(parsed.nintendo_limited(), None);
+    /// assert_eq!(parsed.editor_mode(), None);
+    /// ```
+    pub fn nintendo_limited(&self) -> Option<bool> {
+        self.extra_flag(0)
+    }
+
+    /// Editor mode flag sent as field 13 (the second of *extra_fields*), see [`ServerIdStringParsed::nintendo_limited`].
+    pub fn editor_mode(&self) -> Option<bool> {
+        self.extra_flag(1)
+    }
+
+    /// `0` / `1` flag at `index` of *extra_fields*.
+    fn extra_flag(&self, index: usize) -> Option<bool> {
+        match self.extra_fields.get(index).map(|field| field.trim()) {
+            Some("0") => Some(false),
+            Some("1") => Some(true),
+            _ => None,
+        }
+    }
+
+    /// Default gamemode parsed from *gamemode_numeric* (*gamemode* string is ignored, servers don't always keep them in sync).
+    pub fn gamemode_enum(&self) -> Gamemode {
+        Gamemode::from(self.gamemode_numeric)
+    }
+
+    /// Whether *gamemode* string names the same gamemode as *gamemode_numeric* (case-insensitive).
+    /// `false` for numbers outside 0 - 3 as well.
+    ///
+    /// Mismatch alone doesn't make *server_id_string_parsed_ok* `false`: plenty of servers send e.g. "Survival;1".
+    /// Out of range numbers do.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcpe_motd::parse_server_id_string;
+    ///
+    /// let parsed = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30;3;10;1;world;creative;1").unwrap();
+    /// assert!(parsed.gamemode_matches());
+    ///
+    /// let parsed = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30;3;10;1;world;Survival;1").unwrap();
+    /// assert!(!parsed.gamemode_matches());
+    ///
+    /// let parsed = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30;3;10;1;world;Survival;7").unwrap();
+    /// assert_eq!(parsed.gamemode_numeric, 7);
+    /// assert!(!parsed.gamemode_matches());
+    /// ```
+    pub fn gamemode_matches(&self) -> bool {
+        Gamemode::from_name(&self.gamemode) == Some(self.gamemode_enum())
+    }
+
+    /// One gamemode out of *gamemode* and *gamemode_numeric*, which don't always agree.
+    /// *gamemode_numeric* wins if the server sent it. Otherwise *gamemode* string is used if it names a gamemode (case-insensitive),
+    /// falling back to the defaulted *gamemode_numeric* (Survival).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcpe_motd::{parse_server_id_string, Gamemode};
+    ///
+    /// let parsed = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30;3;10;1;world;Survival;1").unwrap();
+    /// assert_eq!(parsed.canonical_gamemode(), Gamemode::Creative);
+    ///
+    /// // No numeric field, so the string is used
+    /// let parsed = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30;3;10;1;world;Adventure").unwrap();
+    /// assert_eq!(parsed.gamemode_numeric, 0);
+    /// assert_eq!(parsed.canonical_gamemode(), Gamemode::Adventure);
+    ///
+    /// let parsed = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30;3;10;1;world;Hardcore").unwrap();
+    /// assert_eq!(parsed.canonical_gamemode(), Gamemode::Survival);
+    /// ```
+    pub fn canonical_gamemode(&self) -> Gamemode {
+        if self.present_fields().gamemode_numeric {
+            return self.gamemode_enum();
+        }
+
+        Gamemode::from_name(&self.gamemode).unwrap_or_else(|| self.gamemode_enum())
+    }
+
+    /// Whether motd contains any `§` formatting codes (so plain motds can skip formatting entirely).
+    pub fn has_formatting(&self) -> bool {
+        self.motd.contains('§')
+    }
+
+    /// Motd without `§` formatting codes (e.g. `§a`, `§l`), for plain terminals and web pages.
+    /// Trailing `§` without a code character is dropped too.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcpe_motd::parse_server_id_string;
+    ///
+    /// let parsed = parse_server_id_string("MCPE;§l§aHello §räöü§;615;1.20.30").unwrap();
+    ///
+    /// assert_eq!(parsed.motd_plain(), "Hello äöü");
+    /// ```
+    pub fn motd_plain(&self) -> String {
+        let mut plain = String::with_capacity(self.motd.len());
+        let mut chars = self.motd.chars();
+
+        while let Some(c) = chars.next() {
+            if c == '§' {
+                // Skip the code character as well
+                chars.next();
+            } else {
+                plain.push(c);
             }
-        } else {
-            server_id_string_parsed_ok = false;
-            19132
-        },
+        }
 
-        port_v6: if split_server_id_string_size >= 12 {
-            match split_server_id_string[11].parse() {
-                Ok(v) => v,
-                Err(_) => {
-                    return Err(MotdError { code: MotdErrorCode::CantParsePort6, message: String::from("Couldn't parse port_v6 field from server id string") });
-                }
+        plain
+    }
+
+    /// Whether *version_name* is equal to or newer than `target` (e.g. "1.20").
+    /// Versions are compared component by component as numbers, missing components count as 0 (so "1.20" == "1.20.0").
+    /// Suffixes like "-beta" are ignored.
+    /// Returns `None` if either version isn't parseable.
+    pub fn version_at_least(&self, target: &str) -> Option<bool> {
+        let version = parse_version(&self.version_name)?;
+        let target = parse_version(target)?;
+
+        for i in 0..version.len().max(target.len()) {
+            let a = version.get(i).copied().unwrap_or(0);
+            let b = target.get(i).copied().unwrap_or(0);
+
+            if a != b {
+                return Some(a > b);
             }
-        } else {
-            server_id_string_parsed_ok = false;
-            19132
-        },
-    };
+        }
 
-    Ok(UnconnectedPong {
-        id,
-        time_since_start,
-        server_guid,
-        magic: MAGIC,
-        server_id_string_len,
-        server_id_string_raw: server_id_string,
-        server_id_string_parsed_ok,
-        server_id_string_parsed,
-    })
+        Some(true)
+    }
+
+    /// *version_name* as `(major, minor, patch)`, so versions can be compared with `<` / `>`.
+    /// Components after the third one are ignored, `None` if there are less than three or any of them isn't a number (e.g. "1.20" or "Beta").
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcpe_motd::parse_server_id_string;
+    ///
+    /// let parsed = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30").unwrap();
+    /// assert_eq!(parsed.version_tuple(), Some((1, 20, 30)));
+    /// assert!(parsed.version_tuple() >= Some((1, 20, 0)));
+    ///
+    /// let parsed = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30.2").unwrap();
+    /// assert_eq!(parsed.version_tuple(), Some((1, 20, 30)));
+    ///
+    /// let parsed = parse_server_id_string("MCPE;Dedicated Server;615;1.20").unwrap();
+    /// assert_eq!(parsed.version_tuple(), None);
+    /// ```
+    pub fn version_tuple(&self) -> Option<(u32, u32, u32)> {
+        let mut components = self.version_name.trim().split('.').map(|c| c.parse().ok());
+
+        Some((components.next()??, components.next()??, components.next()??))
+    }
 }
 
-/// Returns parsed [server id string](https://wiki.vg/Raknet_Protocol#Unconnected_Pong) or error explaining why it wasn't parsed.
-/// # Arguments
-///
-/// * `addr` - address of the target server.
-///
-/// # Panics
-///
-/// Function can return an error if:
-///  - couldn't send packet to the target server
-///  - couldn't parse response (e.g. invalid unconnected pong packet)
-///
-/// However, it will try to replace invalid data with default ones (e.g. empty port field in server id string will be replaced with 19132) until minecraft can process that data.
-/// Obviously, minecraft won't work with empty server id string or *String* instead of *version_protocol field*.
-///
-/// # Example
-///
-/// ```
-/// use::mcpe_motd::fetch_server_id_string;
-///
-/// let server_id_string = match fetch_server_id_string("127.0.0.1:19132") {
-///     Ok(str) => str,
-///     Err(e) => panic!(e)
-/// };
-///
-/// // Will print -1 / -1 if server id string is invalid (as well as vanilla minecraft will).
-/// println!("There are {} / {} players on the server.",
-/// server_id_string.player_count,
-/// server_id_string.max_player_count);
-/// ```
-pub fn fetch_server_id_string(addr: &str) -> Result<ServerIdStringParsed, MotdError> {
-    let unconected_pong = match fetch_unconected_pong(addr) {
-        Ok(v) => v,
-        Err(e) => { return Err(e); }
-    };
+/// Parses dot-separated version (e.g. "1.20.40") into numbers, stopping at the first component that doesn't start with a digit.
+fn parse_version(version: &str) -> Option<Vec<u32>> {
+    let mut components = Vec::new();
 
-    Ok(unconected_pong.server_id_string_parsed)
-}
\ No newline at end of file
+    for component in version.trim().split('.') {
+        let digits: String = component.chars().take_while(|c| c.is_ascii_digit()).collect();
+
+        match digits.parse() {
+            Ok(v) => components.push(v),
+            Err(_) => break,
+        }
+
+        // Anything after a suffix (e.g. "1.20-beta.1") isn't part of the version
+        if digits.len() != component.len() {
+            break;
+        }
+    }
+
+    if components.is_empty() { None } else { Some(components) }
+}
+
+/// Required fields of [server id string](https://wiki.vg/Raknet_Protocol#Unconnected_Pong), returned by [`parse_server_id_string_summary`].
+/// All of them are always present (otherwise it's an error), nothing is filled with defaults.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ServerIdStringSummary {
+    /// Server minecraft edition (MCPE or MCEE).
+    pub edition: Edition,
+    /// Text that is displayed in the server tab.
+    pub motd: String,
+    /// Minecraft protocol version (e.g. 615).
+    pub protocol_version: u16,
+    /// Minecraft version name (e.g. 1.20.30).
+    pub version_name: String,
+}
+
+/// Parsed [RakNet unconnected pong packet](https://wiki.vg/Raknet_Protocol#Unconnected_Pong).
+/// Has more information than *ServerIdStringParsed*.
+/// Unlike *ServerIdStringParsed*, using *UnconnectedPong* you can check if server id string was parsed correctly (without adding default ones).
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UnconnectedPong {
+    /// Packet id (0x1c).
+    pub id: u8,
+    /// Time since server start in ms.
+    pub time_since_start: u64,
+    /// Server guid.
+    pub server_guid: u64,
+    /// Whoops magic...
+    ///
+    /// With `serde` feature it's (de)serialized as a sequence of 16 numbers rather than bytes, so it looks the same in every format.
+    pub magic: [u8; 16],
+    /// Length of server id string.
+    pub server_id_string_len: u16,
+    /// Length of the whole datagram (header and anything after the server id string included),
+    /// e.g. to keep an eye on the pong growing towards the RakNet MTU.
+    pub raw_packet_len: usize,
+    /// Raw server id string.
+    pub server_id_string_raw: String,
+    /// Whether server id string was parsed correctly (same as `warnings.is_empty()`).
+    pub server_id_string_parsed_ok: bool,
+    /// Every default value parser had to fill in and every other deviation from what vanilla sends.
+    pub warnings: Vec<ParseWarning>,
+    /// Parsed server id string.
+    pub server_id_string_parsed: ServerIdStringParsed,
+    /// Server id string split on `;` exactly as received (empty fields included).
+    /// Only filled in when [`FetchOptions::raw_fields`] is enabled.
+    pub raw_fields: Option<Vec<String>>,
+    /// Address (including the port) the pong was received from, i.e. the endpoint that actually answered.
+    pub source_addr: Option<SocketAddr>,
+    /// Wall-clock round-trip time: from right before the ping was sent until the pong was received.
+    /// Parsing isn't counted, stray pongs discarded by [`FetchOptions::correlate`] are (waiting for the right one is part of the trip).
+    /// Zero when the pong wasn't fetched over the network (see *parse_unconnected_pong*).
+    pub latency: Duration,
+    /// When the pong was received (taken right after it arrived, before parsing).
+    /// `UNIX_EPOCH` when the pong wasn't fetched over the network.
+    pub received_at: SystemTime,
+}
+
+impl UnconnectedPong {
+    /// Creates pong without the network (e.g. for tests or a caching proxy), server id string is serialized from `parsed`
+    /// with [`ServerIdStringParsed::to_server_id_string`]. Network-dependent fields are the same as of [`parse_unconnected_pong`].
+    /// Server id string longer than 65535 bytes (the most its length can say) is cut to fit, at a character boundary.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcpe_motd::{parse_server_id_string, parse_unconnected_pong, UnconnectedPong};
+    ///
+    /// let parsed = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30;3;10;13253860892328930865;Bedrock level;Survival;1;19132;19133").unwrap();
+    /// let pong = UnconnectedPong::new(1000, 0x1234, parsed.clone());
+    ///
+    /// assert_eq!(pong.server_id_string_raw, "MCPE;Dedicated Server;615;1.20.30;3;10;13253860892328930865;Bedrock level;Survival;1;19132;19133");
+    /// assert_eq!(pong.server_id_string_parsed, parsed);
+    /// assert_eq!(parse_unconnected_pong(&pong.to_bytes()).unwrap(), pong);
+    ///
+    /// let parsed = parse_server_id_string(&format!("MCPE;{};615;1.20.30", "a".repeat(70_000))).unwrap();
+    /// let pong = UnconnectedPong::new(1000, 0x1234, parsed);
+    ///
+    /// assert_eq!(pong.server_id_string_len, u16::MAX);
+    /// assert_eq!(pong.server_id_string_raw.len(), 65535);
+    /// assert_eq!(pong.to_bytes().len(), 35 + 65535);
+    /// ```
+    pub fn new(time_since_start: u64, server_guid: u64, parsed: ServerIdStringParsed) -> Self {
+        let mut server_id_string_raw = parsed.to_server_id_string();
+        let (fitting, server_id_string_len) = fit_server_id_string(&server_id_string_raw);
+        server_id_string_raw.truncate(fitting.len());
+
+        UnconnectedPong {
+            id: 0x1c,
+            time_since_start,
+            server_guid,
+            magic: RAKNET_MAGIC,
+            server_id_string_len,
+            raw_packet_len: 35 + server_id_string_raw.len(),
+            server_id_string_raw,
+            server_id_string_parsed_ok: true,
+            warnings: Vec::new(),
+            server_id_string_parsed: parsed,
+            raw_fields: None,
+            source_addr: None,
+            latency: Duration::ZERO,
+            received_at: UNIX_EPOCH,
+        }
+    }
+
+    /// Estimates when the server was started by subtracting *time_since_start* from `fetched_at` (e.g. *received_at*).
+    ///
+    /// It's only as precise as the server's clock and the network: estimate is off by up to the round-trip time,
+    /// and some servers simply echo the time field of the ping instead of their uptime.
+    pub fn estimated_start_time(&self, fetched_at: SystemTime) -> SystemTime {
+        fetched_at.checked_sub(self.uptime()).unwrap_or(UNIX_EPOCH)
+    }
+
+    /// How long the server has been running: *time_since_start*, which is in milliseconds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use mcpe_motd::{parse_server_id_string, UnconnectedPong};
+    ///
+    /// let parsed = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30").unwrap();
+    /// let pong = UnconnectedPong::new(90_000, 0, parsed);
+    ///
+    /// assert_eq!(pong.uptime(), Duration::from_secs(90));
+    /// ```
+    pub fn uptime(&self) -> Duration {
+        Duration::from_millis(self.time_since_start)
+    }
+
+    /// Same as [`UnconnectedPong::estimated_start_time`] counting from *received_at*, or from now if the pong wasn't fetched over the network.
+    /// Uptime longer than the time since `UNIX_EPOCH` gives `UNIX_EPOCH`.
+    pub fn approximate_boot_time(&self) -> SystemTime {
+        let fetched_at = if self.received_at == UNIX_EPOCH { SystemTime::now() } else { self.received_at };
+
+        self.estimated_start_time(fetched_at)
+    }
+
+    /// Key identifying the server itself rather than one of its answers: *server_guid* and *server_unique_id*.
+    /// It doesn't change with the player count, so it collapses the same server seen on several ips or ports.
+    ///
+    /// *server_guid* is usually regenerated when the server restarts, *server_unique_id* is the more stable part of the key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use mcpe_motd::{parse_server_id_string, UnconnectedPong};
+    ///
+    /// let before = UnconnectedPong::new(1000, 0x1234, parse_server_id_string("MCPE;Dedicated Server;615;1.20.30;3;10;13253860892328930865").unwrap());
+    /// let after = UnconnectedPong::new(2000, 0x1234, parse_server_id_string("MCPE;Dedicated Server;615;1.20.30;4;10;13253860892328930865").unwrap());
+    ///
+    /// let servers: HashSet<_> = [&before, &after].iter().map(|pong| pong.identity_key()).collect();
+    /// assert_eq!(servers.len(), 1);
+    /// assert_eq!(before.identity_key(), (0x1234, "13253860892328930865".to_string()));
+    /// ```
+    pub fn identity_key(&self) -> (u64, String) {
+        (self.server_guid, self.server_id_string_parsed.server_unique_id.clone())
+    }
+
+    /// Serializes pong back into the wire format: id, time, server guid, magic, length-prefixed server id string.
+    /// Length is taken from *server_id_string_raw* itself, so edited strings stay consistent.
+    /// Only the first 65535 bytes of longer strings are written (cut at a character boundary), as the length can't say more.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let (server_id_string, server_id_string_len) = fit_server_id_string(&self.server_id_string_raw);
+        let server_id_string = server_id_string.as_bytes();
+
+        let mut bytes = Vec::with_capacity(35 + server_id_string.len());
+        bytes.push(self.id);
+        bytes.extend_from_slice(&self.time_since_start.to_be_bytes());
+        bytes.extend_from_slice(&self.server_guid.to_be_bytes());
+        bytes.extend_from_slice(&self.magic);
+        bytes.extend_from_slice(&server_id_string_len.to_be_bytes());
+        bytes.extend_from_slice(server_id_string);
+
+        bytes
+    }
+
+    /// Address to connect to: `queried_ip` with the port server reported for the address family (see [`ServerIdStringParsed::port_for`]).
+    /// IPv6 literals are put in brackets (`[::1]:19132`).
+    /// If reported port is 0, port the pong came from is used instead ([`DEFAULT_PORT`] if the pong wasn't fetched over the network).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcpe_motd::parse_unconnected_pong;
+    ///
+    /// let mut bytes = vec![0x1c];
+    /// bytes.extend_from_slice(&[0; 16]);
+    /// bytes.extend_from_slice(&[0x00, 0xff, 0xff, 0x00, 0xfe, 0xfe, 0xfe, 0xfe, 0xfd, 0xfd, 0xfd, 0xfd, 0x12, 0x34, 0x56, 0x78]);
+    /// let server_id_string = "MCPE;Dedicated Server;615;1.20.30;3;10;13253860892328930865;Bedrock level;Survival;1;19134;0";
+    /// bytes.extend_from_slice(&(server_id_string.len() as u16).to_be_bytes());
+    /// bytes.extend_from_slice(server_id_string.as_bytes());
+    ///
+    /// let pong = parse_unconnected_pong(&bytes).unwrap();
+    ///
+    /// assert_eq!(pong.connect_address("127.0.0.1", false), "127.0.0.1:19134");
+    /// assert_eq!(pong.connect_address("::1", true), "[::1]:19132");
+    /// ```
+    pub fn connect_address(&self, queried_ip: &str, ipv6: bool) -> String {
+        let port = match self.server_id_string_parsed.port_for(ipv6) {
+            0 => self.source_addr.map_or(DEFAULT_PORT, |addr| addr.port()),
+            port => port,
+        };
+
+        if queried_ip.contains(':') && !queried_ip.starts_with('[') {
+            format!("[{}]:{}", queried_ip, port)
+        } else {
+            format!("{}:{}", queried_ip, port)
+        }
+    }
+
+    /// Returns flat version of the pong (see *FlatPong*), e.g. to put it into csv or arrow columns.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcpe_motd::{parse_server_id_string, UnconnectedPong};
+    ///
+    /// // Player counts weren't sent
+    /// let flat = UnconnectedPong::new(0, 0, parse_server_id_string("MCPE;Dedicated Server;615;1.20.30").unwrap()).flat();
+    /// assert_eq!((flat.player_count, flat.player_count_is_null), (0, true));
+    /// assert_eq!((flat.max_player_count, flat.max_player_count_is_null), (0, true));
+    ///
+    /// // Server really sent -1
+    /// let flat = UnconnectedPong::new(0, 0, parse_server_id_string("MCPE;Dedicated Server;615;1.20.30;-1;10").unwrap()).flat();
+    /// assert_eq!((flat.player_count, flat.player_count_is_null), (-1, false));
+    /// assert_eq!((flat.max_player_count, flat.max_player_count_is_null), (10, false));
+    ///
+    /// // Player count was sent empty
+    /// let flat = UnconnectedPong::new(0, 0, parse_server_id_string("MCPE;Dedicated Server;615;1.20.30;;10").unwrap()).flat();
+    /// assert_eq!((flat.player_count, flat.player_count_is_null), (0, true));
+    /// assert_eq!((flat.max_player_count, flat.max_player_count_is_null), (10, false));
+    /// ```
+    pub fn flat(&self) -> FlatPong {
+        let parsed = &self.server_id_string_parsed;
+
+        FlatPong {
+            time_since_start: self.time_since_start,
+            server_guid: self.server_guid,
+            server_id_string_parsed_ok: self.server_id_string_parsed_ok,
+            edition: parsed.edition.to_string(),
+            motd: parsed.motd.clone(),
+            protocol_version: parsed.protocol_version as i32,
+            version_name: parsed.version_name.clone(),
+            player_count: parsed.player_count_opt().unwrap_or(0),
+            player_count_is_null: parsed.player_count_opt().is_none(),
+            max_player_count: parsed.max_player_count_opt().unwrap_or(0),
+            max_player_count_is_null: parsed.max_player_count_opt().is_none(),
+            server_unique_id: parsed.server_unique_id.clone(),
+            level_name: parsed.level_name.clone(),
+            gamemode: parsed.gamemode.clone(),
+            gamemode_numeric: parsed.gamemode_numeric as i32,
+            port_v4: parsed.port_v4 as i32,
+            port_v6: parsed.port_v6 as i32,
+        }
+    }
+}
+
+/// Human-readable summary of the server (see *ServerIdStringParsed* `Display`), followed by latency if the pong was fetched over the network.
+///
+/// # Example
+///
+/// ```
+/// use mcpe_motd::parse_unconnected_pong;
+///
+/// let mut bytes = vec![0x1c];
+/// bytes.extend_from_slice(&[0; 16]);
+/// bytes.extend_from_slice(&[0x00, 0xff, 0xff, 0x00, 0xfe, 0xfe, 0xfe, 0xfe, 0xfd, 0xfd, 0xfd, 0xfd, 0x12, 0x34, 0x56, 0x78]);
+/// let server_id_string = "MCPE;§aDedicated Server;615;1.20.30;3;10";
+/// bytes.extend_from_slice(&(server_id_string.len() as u16).to_be_bytes());
+/// bytes.extend_from_slice(server_id_string.as_bytes());
+///
+/// let pong = parse_unconnected_pong(&bytes).unwrap();
+///
+/// assert_eq!(pong.to_string(), "\
+/// Motd: Dedicated Server
+/// Edition: MCPE
+/// Version: 1.20.30 (protocol 615)
+/// Players: 3/10
+/// Gamemode: Survival (0)
+/// Ports: 19132 (IPv4), 19132 (IPv6)");
+/// ```
+impl fmt::Display for UnconnectedPong {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.server_id_string_parsed)?;
+
+        if self.source_addr.is_some() {
+            write!(f, "\nLatency: {:?}", self.latency)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Same as [`parse_unconnected_pong`], for datagrams received with your own socket code.
+impl TryFrom<&[u8]> for UnconnectedPong {
+    type Error = MotdError;
+
+    fn try_from(response: &[u8]) -> Result<Self, Self::Error> {
+        parse_unconnected_pong(response)
+    }
+}
+
+/// Flat version of *UnconnectedPong* where every field is a primitive column (strings, `bool`, `i32` and `u64`).
+/// *time_since_start* and *server_guid* stay `u64`, the other numbers are widened to `i32` so they map to common columnar types.
+/// Instead of the `-1` player counts (added when server didn't send them) there are `*_is_null` flags, the count itself is `0` then.
+/// Counts the server did send are kept as they are, even negative ones.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FlatPong {
+    /// Time since server start in ms.
+    pub time_since_start: u64,
+    /// Server guid.
+    pub server_guid: u64,
+    /// Whether server id string was parsed correctly.
+    pub server_id_string_parsed_ok: bool,
+    /// Server minecraft edition (MCPE or MCEE).
+    pub edition: String,
+    /// Text that is displayed in the server tab.
+    pub motd: String,
+    /// Minecraft protocol version (e.g. 615).
+    pub protocol_version: i32,
+    /// Minecraft version name (e.g. 1.20.30).
+    pub version_name: String,
+    /// How many players is playing on the server (`0` if null).
+    pub player_count: i32,
+    /// Whether server didn't send player count.
+    pub player_count_is_null: bool,
+    /// How many players can be playing on the server at the same time (`0` if null).
+    pub max_player_count: i32,
+    /// Whether server didn't send max player count.
+    pub max_player_count_is_null: bool,
+    /// Some unique id.
+    pub server_unique_id: String,
+    /// Map name.
+    pub level_name: String,
+    /// Default gamemode.
+    pub gamemode: String,
+    /// Default gamemode but number.
+    pub gamemode_numeric: i32,
+    /// Port used for IPv4 communication.
+    pub port_v4: i32,
+    /// Port used for IPv6 communication.
+    pub port_v6: i32,
+}
+
+/// Settings for fetching an unconnected pong.
+/// `FetchOptions::default()` behaves exactly like [`fetch_unconected_pong`], free functions are just shortcuts for common options.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use mcpe_motd::FetchOptions;
+///
+/// let pong = FetchOptions::new()
+///     .timeout(Duration::from_secs(1))
+///     .retries(2)
+///     .client_guid(0x1234)
+///     .raw_fields(true)
+///     .fetch("127.0.0.1:19132")
+///     .unwrap();
+///
+/// println!("Server sent {} fields.", pong.raw_fields.unwrap().len());
+/// ```
+#[derive(Debug, Clone)]
+pub struct FetchOptions {
+    raw_fields: bool,
+    ttl: Option<u32>,
+    #[cfg(feature = "tos")]
+    tos: Option<u8>,
+    retries: u32,
+    retry_delay: Duration,
+    retry_max_delay: Duration,
+    retry_jitter: f64,
+    correlate: bool,
+    drain_stray: bool,
+    rate_limit: Option<RateLimiter>,
+    timeout: Duration,
+    send_timeout: Option<Duration>,
+    bind_addr: Option<SocketAddr>,
+    client_guid: u64,
+    ping_time: Option<u64>,
+    magic: [u8; 16],
+    buffer_size: usize,
+    strict_utf8: bool,
+    infer_string_length: bool,
+    lenient: bool,
+    strict: bool,
+    validate_ports: bool,
+    verify_roundtrip: bool,
+    return_raw_on_error: bool,
+    trust_queried_port: bool,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+impl Default for FetchOptions {
+    fn default() -> Self {
+        FetchOptions {
+            raw_fields: false,
+            ttl: None,
+            #[cfg(feature = "tos")]
+            tos: None,
+            retries: 0,
+            retry_delay: Duration::from_millis(100),
+            retry_max_delay: Duration::from_secs(2),
+            retry_jitter: 0.2,
+            correlate: false,
+            drain_stray: false,
+            rate_limit: None,
+            timeout: DEFAULT_TIMEOUT,
+            send_timeout: None,
+            bind_addr: None,
+            client_guid: 0,
+            ping_time: None,
+            magic: RAKNET_MAGIC,
+            buffer_size: DEFAULT_BUFFER_SIZE,
+            strict_utf8: false,
+            infer_string_length: false,
+            lenient: false,
+            strict: false,
+            validate_ports: false,
+            verify_roundtrip: false,
+            return_raw_on_error: false,
+            trust_queried_port: false,
+            cancel: None,
+        }
+    }
+}
+
+impl FetchOptions {
+    /// Creates options with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Size of the buffer the pong is received into (2048 bytes by default, at least 36).
+    /// Pong that fills it completely is treated as truncated, raise it for servers with really long server id strings.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use mcpe_motd::FetchOptions;
+    ///
+    /// let pong = FetchOptions::new().buffer_size(8192).fetch("127.0.0.1:19132")?;
+    ///
+    /// println!("{}", pong.server_id_string_parsed.motd);
+    /// # Ok::<(), mcpe_motd::MotdError>(())
+    /// ```
+    pub fn buffer_size(mut self, size: usize) -> Self {
+        self.buffer_size = size.max(36);
+        self
+    }
+
+    /// Fail with *MotdErrorCode::InvalidUtf8* if server id string isn't valid UTF-8,
+    /// instead of replacing invalid bytes with `�` (disabled by default).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use mcpe_motd::{FetchOptions, MotdErrorCode};
+    ///
+    /// match FetchOptions::new().strict_utf8(true).fetch("127.0.0.1:19132") {
+    ///     Ok(pong) => println!("{}", pong.server_id_string_parsed.motd),
+    ///     Err(e) if e.code == MotdErrorCode::InvalidUtf8 => println!("Motd isn't valid UTF-8: {}", e),
+    ///     Err(e) => println!("{}", e),
+    /// }
+    /// ```
+    pub fn strict_utf8(mut self, enabled: bool) -> Self {
+        self.strict_utf8 = enabled;
+        self
+    }
+
+    /// Compatibility shim for custom servers that send server id string right after the magic, without the 2 byte length (disabled by default).
+    /// When the length is larger than the rest of the packet, everything after the magic is taken as server id string
+    /// instead of failing with *MotdErrorCode::LengthMismatch*. *server_id_string_len* of the pong is the inferred length then.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use mcpe_motd::FetchOptions;
+    ///
+    /// let pong = FetchOptions::new().infer_string_length(true).fetch("127.0.0.1:19132")?;
+    ///
+    /// println!("{} ({} bytes)", pong.server_id_string_parsed.motd, pong.server_id_string_len);
+    /// # Ok::<(), mcpe_motd::MotdError>(())
+    /// ```
+    pub fn infer_string_length(mut self, enabled: bool) -> Self {
+        self.infer_string_length = enabled;
+        self
+    }
+
+    /// Replace optional numeric fields (player counts, *gamemode_numeric* and ports) that aren't numbers with defaults
+    /// and record a [`ParseWarning`] (e.g. *ParseWarning::InvalidPlayerCount*) instead of failing the whole fetch (disabled by default).
+    /// Edition, motd, *protocol_version* and *version_name* are required, so an invalid *protocol_version* is still an error.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use mcpe_motd::FetchOptions;
+    ///
+    /// let pong = FetchOptions::new().lenient(true).fetch("127.0.0.1:19132")?;
+    ///
+    /// for warning in &pong.warnings {
+    ///     println!("{:?}", warning);
+    /// }
+    /// # Ok::<(), mcpe_motd::MotdError>(())
+    /// ```
+    pub fn lenient(mut self, enabled: bool) -> Self {
+        self.lenient = enabled;
+        self
+    }
+
+    /// Fail with *MotdErrorCode::MissingField* if any optional field of server id string is missing (or empty number),
+    /// instead of filling it with default value (disabled by default). Useful for tools that check servers send everything they should.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use mcpe_motd::{FetchOptions, MotdErrorCode};
+    ///
+    /// if let Err(e) = FetchOptions::new().strict(true).fetch("127.0.0.1:19132") {
+    ///     if e.code == MotdErrorCode::MissingField {
+    ///         println!("Server doesn't send everything: {}", e);
+    ///     }
+    /// }
+    /// ```
+    pub fn strict(mut self, enabled: bool) -> Self {
+        self.strict = enabled;
+        self
+    }
+
+    /// Treat reported port 0 as invalid: *server_id_string_parsed_ok* is `false` then (disabled by default).
+    /// See [`ServerIdStringParsed::ports_valid`].
+    pub fn validate_ports(mut self, enabled: bool) -> Self {
+        self.validate_ports = enabled;
+        self
+    }
+
+    /// Check that [`ServerIdStringParsed::to_server_id_string`] gives back exactly what server sent, apart from the trailing `;` (disabled by default).
+    /// If it doesn't, something was lost or changed while parsing (e.g. padded numbers or defaulted fields),
+    /// *ParseWarning::RoundtripMismatch* is recorded and *server_id_string_parsed_ok* is `false`.
+    /// Checked before [`FetchOptions::trust_queried_port`] replaces the ports.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use mcpe_motd::{FetchOptions, ParseWarning};
+    ///
+    /// let pong = FetchOptions::new().verify_roundtrip(true).fetch("127.0.0.1:19132")?;
+    ///
+    /// if pong.warnings.contains(&ParseWarning::RoundtripMismatch) {
+    ///     println!("Sent {:?}, parsed {:?}", pong.server_id_string_raw, pong.server_id_string_parsed.to_server_id_string());
+    /// }
+    /// # Ok::<(), mcpe_motd::MotdError>(())
+    /// ```
+    pub fn verify_roundtrip(mut self, enabled: bool) -> Self {
+        self.verify_roundtrip = enabled;
+        self
+    }
+
+    /// Keep the whole received packet in *raw_bytes* of the error if it couldn't be parsed (disabled by default),
+    /// so the exact packet can be saved and examined later (e.g. with [`parse_unconnected_pong_hex`]).
+    /// Truncated packets (*MotdErrorCode::PacketTruncated*) are kept as well, errors that happened before anything was received
+    /// (timeouts, resolving, sending) don't have it.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use mcpe_motd::FetchOptions;
+    ///
+    /// if let Err(e) = FetchOptions::new().return_raw_on_error(true).fetch("127.0.0.1:19132") {
+    ///     if let Some(raw) = e.raw_bytes {
+    ///         std::fs::write("broken_pong.bin", raw)?;
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn return_raw_on_error(mut self, enabled: bool) -> Self {
+        self.return_raw_on_error = enabled;
+        self
+    }
+
+    /// Replace reported *port_v4* and *port_v6* with the port the ping was sent to (disabled by default),
+    /// for servers behind a proxy (e.g. Waterdog) that advertises the ports of the server behind it.
+    /// *server_id_string_raw* is kept as received.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use mcpe_motd::FetchOptions;
+    ///
+    /// let pong = FetchOptions::new().trust_queried_port(true).fetch("127.0.0.1:19134")?;
+    ///
+    /// assert_eq!(pong.server_id_string_parsed.port_v4, 19134);
+    /// # Ok::<(), mcpe_motd::MotdError>(())
+    /// ```
+    pub fn trust_queried_port(mut self, enabled: bool) -> Self {
+        self.trust_queried_port = enabled;
+        self
+    }
+
+    /// Aborts the fetch with *MotdErrorCode::Cancelled* as soon as `flag` is set (e.g. when user selected another server in a GUI),
+    /// instead of waiting for the whole timeout. Remaining retries aren't made either.
+    /// While waiting for the response the flag is checked every 20 ms.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::net::UdpSocket;
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::time::{Duration, Instant};
+    /// use mcpe_motd::{FetchOptions, MotdErrorCode};
+    ///
+    /// // Server that never answers
+    /// let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+    /// let addr = server.local_addr().unwrap();
+    ///
+    /// let cancel = Arc::new(AtomicBool::new(false));
+    /// let flag = cancel.clone();
+    /// std::thread::spawn(move || {
+    ///     std::thread::sleep(Duration::from_millis(100));
+    ///     flag.store(true, Ordering::Relaxed);
+    /// });
+    ///
+    /// let start = Instant::now();
+    /// let e = FetchOptions::new().timeout(Duration::from_secs(5)).cancel(cancel).fetch(addr).unwrap_err();
+    ///
+    /// assert_eq!(e.code, MotdErrorCode::Cancelled);
+    /// assert!(start.elapsed() < Duration::from_secs(5));
+    /// ```
+    pub fn cancel(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(flag);
+        self
+    }
+
+    /// Keep the raw split server id string in [`UnconnectedPong::raw_fields`] (disabled by default to avoid the allocation).
+    pub fn raw_fields(mut self, enabled: bool) -> Self {
+        self.raw_fields = enabled;
+        self
+    }
+
+    /// IP time-to-live of the outgoing ping (OS default if not set).
+    /// A low TTL keeps the ping inside the local network.
+    pub fn ttl(mut self, ttl: u32) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// IP type of service byte of the outgoing ping (OS default if not set), e.g. to test QoS policies.
+    /// DSCP is the upper 6 bits, so DSCP `46` (EF) is `46 << 2`.
+    ///
+    /// For IPv6 targets it's set as traffic class (Linux, Android, macOS and BSDs only).
+    ///
+    /// Requires `tos` feature. Linux, macOS and BSDs honor it, Windows usually ignores it unless a QoS policy allows it.
+    #[cfg(feature = "tos")]
+    pub fn tos(mut self, tos: u8) -> Self {
+        self.tos = Some(tos);
+        self
+    }
+
+    /// How many times to try again if fetch fails (0 by default).
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Delay before the first retry (100 ms by default). Every next delay is twice as long, up to [`FetchOptions::retry_max_delay`]:
+    /// `min(retry_delay * 2^(retry - 1), retry_max_delay)`, so 100 ms, 200 ms, 400 ms and so on.
+    pub fn retry_delay(mut self, delay: Duration) -> Self {
+        self.retry_delay = delay;
+        self
+    }
+
+    /// Longest delay between retries (2 s by default), see [`FetchOptions::retry_delay`].
+    /// Setting it to *retry_delay* makes every delay the same.
+    pub fn retry_max_delay(mut self, delay: Duration) -> Self {
+        self.retry_max_delay = delay;
+        self
+    }
+
+    /// Randomizes every retry delay (after backoff) by up to ± `fraction` of it (0.2 by default, so 100 ms becomes 80 - 120 ms).
+    /// This keeps many scanners started at the same time from retrying in sync. `0.0` disables jitter.
+    /// `fraction` is clamped to 0.0 - 1.0, NaN and infinities disable jitter.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::net::UdpSocket;
+    /// # use std::time::Duration;
+    /// use mcpe_motd::{FetchOptions, MotdErrorCode};
+    ///
+    /// # // Fake server that never answers
+    /// # let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+    /// # let addr = server.local_addr().unwrap();
+    /// let e = FetchOptions::new()
+    ///     .timeout(Duration::from_millis(50))
+    ///     .retries(1)
+    ///     .retry_delay(Duration::from_millis(10))
+    ///     .retry_jitter(f64::NAN)
+    ///     .fetch(addr)
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(e.code, MotdErrorCode::Timeout);
+    /// ```
+    pub fn retry_jitter(mut self, fraction: f64) -> Self {
+        self.retry_jitter = if fraction.is_finite() { fraction.clamp(0.0, 1.0) } else { 0.0 };
+        self
+    }
+
+    /// How long to wait for the pong (5 seconds by default).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// How long sending the ping may block (e.g. on a full socket buffer) before fetch fails with *MotdErrorCode::Timeout*,
+    /// same as [`FetchOptions::timeout`] by default.
+    pub fn send_timeout(mut self, timeout: Duration) -> Self {
+        self.send_timeout = Some(timeout);
+        self
+    }
+
+    /// Local address (ip and port) the socket is bound to instead of a random port on any address,
+    /// e.g. for firewalls that only let UDP out from a specific ip or port. Must be of the same family as the target server.
+    ///
+    /// Not used by [`FetchOptions::fetch_with_socket`] and the like, their socket is already bound.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcpe_motd::{FetchOptions, MotdErrorCode};
+    ///
+    /// // IPv6 socket can't reach IPv4 server
+    /// let e = FetchOptions::new().bind_addr("[::1]:0".parse().unwrap()).fetch("127.0.0.1:19132").unwrap_err();
+    ///
+    /// assert_eq!(e.code, MotdErrorCode::CantBind);
+    ///
+    /// // Documentation address (TEST-NET-1) isn't assigned to this machine, so it can't be bound
+    /// let e = FetchOptions::new().bind_addr("192.0.2.1:0".parse().unwrap()).fetch("127.0.0.1:19132").unwrap_err();
+    ///
+    /// assert_eq!(e.code, MotdErrorCode::CantBind);
+    /// assert!(e.message.starts_with("Couldn't bind to 192.0.2.1:0"));
+    /// ```
+    pub fn bind_addr(mut self, addr: SocketAddr) -> Self {
+        self.bind_addr = Some(addr);
+        self
+    }
+
+    /// Client guid sent in the ping (0 by default).
+    ///
+    /// It's written big-endian into the last 8 bytes of the ping (bytes 25 - 32, right after the magic).
+    /// Servers don't echo it back, but some of them and anti-DDoS proxies log it or key on it,
+    /// so distinct guids tell concurrent queries apart on their side.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use mcpe_motd::FetchOptions;
+    ///
+    /// let pong = FetchOptions::new().client_guid(0xdeadbeef).fetch("127.0.0.1:19132")?;
+    ///
+    /// println!("{}", pong.server_id_string_parsed.motd);
+    /// # Ok::<(), mcpe_motd::MotdError>(())
+    /// ```
+    pub fn client_guid(mut self, guid: u64) -> Self {
+        self.client_guid = guid;
+        self
+    }
+
+    /// Time field sent in the ping (16 by default).
+    ///
+    /// Servers echo it back as *time_since_start* of the pong, so giving every in-flight ping its own value
+    /// tells the answers apart when many pings are sent on one socket.
+    /// With [`FetchOptions::correlate`] enabled, only a pong echoing exactly this value is accepted.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use mcpe_motd::FetchOptions;
+    ///
+    /// let pong = FetchOptions::new().ping_time(42).fetch("127.0.0.1:19132")?;
+    ///
+    /// assert_eq!(pong.time_since_start, 42);
+    /// # Ok::<(), mcpe_motd::MotdError>(())
+    /// ```
+    pub fn ping_time(mut self, time: u64) -> Self {
+        self.ping_time = Some(time);
+        self
+    }
+
+    /// Offline message magic sent in the ping and expected in the pong, for modified servers and proxies that use their own.
+    /// Default is the vanilla [`RAKNET_MAGIC`], pongs with any other magic fail with *MotdErrorCode::InvalidMagic*.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use mcpe_motd::FetchOptions;
+    ///
+    /// let magic = [0x42; 16];
+    /// let pong = FetchOptions::new().magic(magic).fetch("127.0.0.1:19132")?;
+    ///
+    /// assert_eq!(pong.magic, magic);
+    /// # Ok::<(), mcpe_motd::MotdError>(())
+    /// ```
+    pub fn magic(mut self, magic: [u8; 16]) -> Self {
+        self.magic = magic;
+        self
+    }
+
+    /// Match the pong to this request and ignore stray pongs (e.g. late answers to previous requests on a shared socket).
+    ///
+    /// RakNet doesn't echo client guid, but it does echo the time field of the ping, so a random nonce is sent there
+    /// (or [`FetchOptions::ping_time`] if it was set) and only a pong from the queried address with the same time is accepted.
+    /// Because of that *time_since_start* of the pong is the nonce, not the server time (disabled by default).
+    pub fn correlate(mut self, enabled: bool) -> Self {
+        self.correlate = enabled;
+        self
+    }
+
+    /// Ignore packets from the queried server that aren't unconnected pongs (wrong packet id, no magic or too short)
+    /// and keep waiting for one until the timeout, instead of failing on the first one (disabled by default).
+    /// Packets from other addresses and, with [`FetchOptions::correlate`], pongs to other pings are always ignored.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use mcpe_motd::FetchOptions;
+    ///
+    /// let pong = FetchOptions::new().drain_stray(true).fetch("127.0.0.1:19132")?;
+    ///
+    /// println!("{}", pong.server_id_string_parsed.motd);
+    /// # Ok::<(), mcpe_motd::MotdError>(())
+    /// ```
+    pub fn drain_stray(mut self, enabled: bool) -> Self {
+        self.drain_stray = enabled;
+        self
+    }
+
+    /// Wait for `limiter` before sending every ping (retries included).
+    /// Options cloned for several workers share the limiter, so the limit applies to all of them together.
+    pub fn rate_limit(mut self, limiter: RateLimiter) -> Self {
+        self.rate_limit = Some(limiter);
+        self
+    }
+
+    /// Returns parsed [RakNet unconnected pong packet](https://wiki.vg/Raknet_Protocol#Unconnected_Pong) fetched with these options.
+    /// See [`fetch_unconected_pong`] for details.
+    pub fn fetch(&self, addr: impl ToSocketAddrs) -> Result<UnconnectedPong> {
+        let addr = resolve(addr)?;
+
+        self.with_retries(|| self.fetch_once(addr))
+    }
+
+    /// Same as [`FetchOptions::fetch`], but sends and receives on `socket` instead of binding a new one
+    /// (e.g. to reuse one socket for many queries or to send from a specific interface or port).
+    ///
+    /// Read timeout of the socket is set to [`FetchOptions::timeout`], as well as *ttl* and *tos* if they were set.
+    /// Packets coming from any other address than `addr` are ignored.
+    /// If the socket is shared, enable [`FetchOptions::correlate`] so that late pongs of previous queries to the same server aren't taken for this one.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::net::UdpSocket;
+    /// use mcpe_motd::FetchOptions;
+    ///
+    /// let options = FetchOptions::new().correlate(true);
+    /// let socket = UdpSocket::bind("0.0.0.0:0")?;
+    ///
+    /// for addr in ["127.0.0.1:19132", "127.0.0.1:19133"] {
+    ///     if let Ok(pong) = options.fetch_with_socket(&socket, addr) {
+    ///         println!("{}: {}", addr, pong.server_id_string_parsed.motd);
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn fetch_with_socket(&self, socket: &UdpSocket, addr: impl ToSocketAddrs) -> Result<UnconnectedPong> {
+        let addr = resolve(addr)?;
+
+        self.with_retries(|| self.exchange(socket, addr, false))
+    }
+
+    /// Same as [`FetchOptions::fetch_with_socket`], but the pong is received into `buffer` instead of a newly allocated one
+    /// (*buffer_size* is ignored, the whole `buffer` is used), so a scan reusing one socket and one buffer doesn't allocate them per query.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::net::UdpSocket;
+    /// use mcpe_motd::FetchOptions;
+    ///
+    /// let options = FetchOptions::new().correlate(true);
+    /// let socket = UdpSocket::bind("0.0.0.0:0")?;
+    /// let mut buffer = [0; 2048];
+    ///
+    /// for port in 19132..=19140 {
+    ///     if let Ok(pong) = options.fetch_with_buffer(&socket, ("127.0.0.1", port), &mut buffer) {
+    ///         println!("{}: {}", port, pong.server_id_string_parsed.motd);
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn fetch_with_buffer(&self, socket: &UdpSocket, addr: impl ToSocketAddrs, buffer: &mut [u8]) -> Result<UnconnectedPong> {
+        let addr = resolve(addr)?;
+
+        self.with_retries(|| self.exchange_into(socket, addr, false, buffer))
+    }
+
+    /// Same as [`FetchOptions::fetch_with_socket`], but `socket` is already connected (with *UdpSocket::connect*) to the server,
+    /// e.g. a hole-punched socket or one of an existing RakNet session, whose NAT mapping a fresh socket would break.
+    ///
+    /// Ping is sent with *send* and the pong is received with *recv* instead of *send_to* / *recv_from*,
+    /// so the system itself drops packets from other addresses. Target is the peer address of the socket.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::net::UdpSocket;
+    /// use mcpe_motd::FetchOptions;
+    ///
+    /// let socket = UdpSocket::bind("0.0.0.0:0")?;
+    /// socket.connect("127.0.0.1:19132")?;
+    ///
+    /// let pong = FetchOptions::new().fetch_connected(&socket)?;
+    ///
+    /// println!("{}", pong.server_id_string_parsed.motd);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn fetch_connected(&self, socket: &UdpSocket) -> Result<UnconnectedPong> {
+        let addr = match socket.peer_addr() {
+            Ok(addr) => addr,
+            Err(e) => { return Err(MotdError::new(MotdErrorCode::CantSendTo, format!("Socket isn't connected: {}", e))); }
+        };
+
+        self.with_retries(|| self.exchange(socket, addr, true))
+    }
+
+    /// Same as [`FetchOptions::fetch`], but returns only the parsed server id string (like *fetch_server_id_string*).
+    pub fn fetch_server_id_string(&self, addr: impl ToSocketAddrs) -> Result<ServerIdStringParsed> {
+        Ok(self.fetch(addr)?.server_id_string_parsed)
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel.as_ref().is_some_and(|flag| flag.load(Ordering::Relaxed))
+    }
+
+    fn cancelled_error(&self, addr: SocketAddr) -> MotdError {
+        MotdError::new(MotdErrorCode::Cancelled, format!("Fetch from {} was cancelled", addr))
+    }
+
+    fn with_retries(&self, mut fetch: impl FnMut() -> Result<UnconnectedPong>) -> Result<UnconnectedPong> {
+        let mut attempt = 0;
+
+        loop {
+            let result = fetch();
+
+            if let Err(e) = &result {
+                warn!("fetch failed: attempt={} code={:?} message={}", attempt + 1, e.code, e.message);
+            }
+
+            match result {
+                Ok(pong) => return Ok(pong),
+                Err(e) if e.code == MotdErrorCode::Cancelled => return Err(e),
+                Err(mut e) if attempt >= self.retries => {
+                    if self.retries > 0 {
+                        e.message = format!("{} (after {} attempts)", e.message, attempt + 1);
+                    }
+
+                    return Err(e);
+                }
+                Err(_) => {
+                    let delay = retry::backoff(self.retry_delay, attempt, self.retry_max_delay);
+
+                    attempt += 1;
+                    thread::sleep(retry::jittered(delay, self.retry_jitter));
+                }
+            }
+        }
+    }
+
+    fn fetch_once(&self, addr: SocketAddr) -> Result<UnconnectedPong> {
+        let bind_addr = self.bind_addr.unwrap_or_else(|| any_addr(addr));
+
+        if bind_addr.is_ipv4() != addr.is_ipv4() {
+            return Err(MotdError::new(MotdErrorCode::CantBind, format!("Can't reach {} from {}, they are of different families", addr, bind_addr)));
+        }
+
+        let socket = match UdpSocket::bind(bind_addr) {
+            Ok(sock) => sock,
+            Err(e) => { return Err(MotdError::new(MotdErrorCode::CantBind, format!("Couldn't bind to {}: {}", bind_addr, e))); }
+        };
+
+        self.exchange(&socket, addr, false)
+    }
+
+    /// Same as [`FetchOptions::exchange_into`] with a new buffer of *buffer_size* bytes.
+    fn exchange(&self, socket: &UdpSocket, addr: SocketAddr, connected: bool) -> Result<UnconnectedPong> {
+        self.exchange_into(socket, addr, connected, &mut vec![0; self.buffer_size])
+    }
+
+    /// Same as [`FetchOptions::send_and_receive`], then parses the pong.
+    fn exchange_into(&self, socket: &UdpSocket, addr: SocketAddr, connected: bool, response: &mut [u8]) -> Result<UnconnectedPong> {
+        let (size, src, latency) = self.send_and_receive(socket, addr, connected, response)?;
+        let received_at = SystemTime::now();
+
+        // Parse errors keep the packet for offline analysis if asked to
+        let raw_bytes = || self.return_raw_on_error.then(|| response[..size].to_vec());
+
+        // Datagrams longer than the buffer are silently cut off
+        if size == response.len() {
+            return Err(MotdError { raw_bytes: raw_bytes(), ..MotdError::new(MotdErrorCode::PacketTruncated, format!("Response of {} filled the whole {} byte buffer", addr, size)) });
+        }
+
+        let mut pong = match parse_pong(&response[..size], self) {
+            Ok(pong) => pong,
+            Err(e) => return Err(MotdError { raw_bytes: raw_bytes(), ..e }),
+        };
+
+        if self.strict {
+            if let Some(field) = first_defaulted_field(&pong.server_id_string_raw) {
+                return Err(MotdError { raw: Some(pong.server_id_string_raw), raw_bytes: raw_bytes(), ..MotdError::new(MotdErrorCode::MissingField, format!("Server id string of {} has no {} field", addr, field)) });
+            }
+        }
+
+        // Trailing `;` most servers send is dropped while parsing, so it isn't a mismatch
+        let raw = &pong.server_id_string_raw;
+        if self.verify_roundtrip && pong.server_id_string_parsed.to_server_id_string() != raw.strip_suffix(';').unwrap_or(raw) {
+            pong.warnings.push(ParseWarning::RoundtripMismatch);
+            pong.server_id_string_parsed_ok = false;
+        }
+
+        if self.trust_queried_port {
+            pong.server_id_string_parsed.port_v4 = addr.port();
+            pong.server_id_string_parsed.port_v6 = addr.port();
+        }
+
+        if self.validate_ports && !pong.server_id_string_parsed.ports_valid() {
+            pong.warnings.push(ParseWarning::InvalidPort);
+            pong.server_id_string_parsed_ok = false;
+        }
+
+        if self.raw_fields {
+            pong.raw_fields = Some(split_server_id_string(&pong.server_id_string_raw));
+        }
+
+        pong.source_addr = Some(src);
+        pong.latency = latency;
+        pong.received_at = received_at;
+
+        Ok(pong)
+    }
+
+    /// Sends one ping on `socket` and waits for the answer of `addr`, receiving it into `response`.
+    /// Returns its size, source and round-trip time. `connected` sockets are used without addresses.
+    fn send_and_receive(&self, socket: &UdpSocket, addr: SocketAddr, connected: bool, response: &mut [u8]) -> Result<(usize, SocketAddr, Duration)> {
+        // Zero timeout can't be set on a socket, and there's no time to wait anyway
+        if self.timeout.is_zero() || socket.set_read_timeout(Some(self.timeout)).is_err() {
+            return Err(MotdError::new(MotdErrorCode::Timeout, format!("Couldn't wait for {} with timeout {:?}", addr, self.timeout)));
+        }
+
+        let send_timeout = self.send_timeout.unwrap_or(self.timeout);
+
+        if send_timeout.is_zero() || socket.set_write_timeout(Some(send_timeout)).is_err() {
+            return Err(MotdError::new(MotdErrorCode::Timeout, format!("Couldn't send to {} with timeout {:?}", addr, send_timeout)));
+        }
+
+        if let Some(ttl) = self.ttl {
+            if socket.set_ttl(ttl).is_err() {
+                return Err(MotdError::new(MotdErrorCode::CantBind, format!("Couldn't set ttl to {}", ttl)));
+            }
+        }
+
+        #[cfg(feature = "tos")]
+        if let Some(tos) = self.tos {
+            if set_tos(socket, addr, tos).is_err() {
+                return Err(MotdError::new(MotdErrorCode::CantBind, format!("Couldn't set tos to {}", tos)));
+            }
+        }
+
+        // Nonce put into the time field of the ping
+        let nonce = if self.correlate { Some(self.ping_time.unwrap_or_else(retry::next_random)) } else { None };
+
+        let mut ping = build_unconnected_ping(self.client_guid, nonce.or(self.ping_time).unwrap_or(DEFAULT_PING_TIME));
+        ping[9..25].copy_from_slice(&self.magic);
+
+        if let Some(limiter) = &self.rate_limit {
+            limiter.acquire();
+        }
+
+        if self.is_cancelled() {
+            return Err(self.cancelled_error(addr));
+        }
+
+        let sent_at = Instant::now();
+
+        let sent = if connected { socket.send(&ping) } else { socket.send_to(&ping, addr) };
+
+        match sent {
+            Ok(_) => (),
+            Err(e) if e.kind() == ErrorKind::WouldBlock || e.kind() == ErrorKind::TimedOut => {
+                return Err(MotdError::new(MotdErrorCode::Timeout, format!("Couldn't send to {} within {:?}", addr, send_timeout)));
+            }
+            Err(e) => { return Err(MotdError::new(MotdErrorCode::CantSendTo, format!("Couldn't send to {}: {}", addr, e))); }
+        }
+
+        debug!("sent ping: addr={} bytes={}", addr, ping.len());
+
+        let deadline = Instant::now() + self.timeout;
+        let timeout_error = || MotdError::new(MotdErrorCode::Timeout, format!("{} didn't respond within {:?}", addr, self.timeout));
+
+        let (size, src) = loop {
+            // Wait in short steps, so the cancel flag is noticed
+            if self.cancel.is_some() {
+                if self.is_cancelled() {
+                    return Err(self.cancelled_error(addr));
+                }
+
+                let remaining = deadline.saturating_duration_since(Instant::now());
+
+                if remaining.is_zero() || socket.set_read_timeout(Some(remaining.min(CANCEL_POLL_INTERVAL))).is_err() {
+                    return Err(timeout_error());
+                }
+            }
+
+            let received = if connected { socket.recv(response).map(|size| (size, addr)) } else { socket.recv_from(response) };
+
+            let (size, src) = match received {
+                Ok(v) => v,
+                Err(e) if e.kind() == ErrorKind::WouldBlock || e.kind() == ErrorKind::TimedOut => {
+                    if self.cancel.is_some() {
+                        continue;
+                    }
+
+                    return Err(timeout_error());
+                }
+                Err(e) => { return Err(MotdError::new(MotdErrorCode::CantReceive, format!("Couldn't receive from {}: {}", addr, e))); }
+            };
+
+            // Only the queried server can answer, and it echoes time field of the ping, so anything else is a stray packet
+            let stray = !same_addr(src, addr)
+                || nonce.is_some_and(|nonce| size < 9 || response[1..9] != nonce.to_be_bytes())
+                || (self.drain_stray && !is_pong(&response[..size], &self.magic));
+
+            if !stray {
+                debug!("received packet: addr={} bytes={}", addr, size);
+                break (size, src);
+            }
+
+            debug!("ignored stray packet: addr={} src={} bytes={}", addr, src, size);
+
+            // Stray packets shouldn't extend the timeout
+            let remaining = deadline.saturating_duration_since(Instant::now());
+
+            if remaining.is_zero() || socket.set_read_timeout(Some(remaining)).is_err() {
+                return Err(timeout_error());
+            }
+        };
+
+        Ok((size, src, sent_at.elapsed()))
+    }
+}
+
+/// Returns parsed [RakNet unconnected pong packet](https://wiki.vg/Raknet_Protocol#Unconnected_Pong) or error explaining why packet wasn't parsed.
+///
+/// # Arguments
+///
+/// * `addr` - address of the target server (`&str` like "127.0.0.1:19132", `SocketAddr`, `(IpAddr, u16)` or anything else implementing `ToSocketAddrs`).
+///
+/// # Panics
+///
+/// Function can return an error if:
+///  - couldn't resolve `addr` (*MotdErrorCode::CantResolveAddress*)
+///  - couldn't send packet to the target server
+///  - server didn't respond within [`DEFAULT_TIMEOUT`] (see *fetch_unconected_pong_timeout*)
+///  - response didn't fit into the receive buffer (see [`FetchOptions::buffer_size`])
+///  - couldn't parse response (e.g. invalid unconnected pong packet)
+///
+/// However, it will try to replace invalid data with default ones (e.g. empty port field in server id string will be replaced with [`DEFAULT_PORT`]) until minecraft can process that data.
+/// Obviously, minecraft won't work with empty server id string or *String* instead of *version_protocol field*.
+///
+/// # Example
+///
+/// ```no_run
+/// use::mcpe_motd::fetch_unconected_pong;
+///
+/// let pong = fetch_unconected_pong("127.0.0.1:19132")?;
+///
+/// println!("Server id string was correctly parsed (true / false): {}.", pong.server_id_string_parsed_ok);
+/// println!("Raw server id string: {}.", pong.server_id_string_raw);
+/// println!("Server guid: {}.", pong.server_guid);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn fetch_unconected_pong(addr: impl ToSocketAddrs) -> Result<UnconnectedPong> {
+    fetch_unconected_pong_timeout(addr, DEFAULT_TIMEOUT)
+}
+
+/// Same as *fetch_unconected_pong*, but waits for the response only for `timeout` instead of [`DEFAULT_TIMEOUT`].
+///
+/// # Arguments
+///
+/// * `addr` - address of the target server.
+/// * `timeout` - how long to wait for the response.
+///
+/// # Panics
+///
+/// Function can return the same errors as *fetch_unconected_pong*.
+/// If server didn't respond in time, error code is *MotdErrorCode::Timeout*, so "server is down" can be told apart from "server sent garbage".
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use mcpe_motd::{fetch_unconected_pong_timeout, MotdErrorCode};
+///
+/// match fetch_unconected_pong_timeout("127.0.0.1:19132", Duration::from_secs(1)) {
+///     Ok(pong) => println!("Online: {}", pong.server_id_string_parsed.motd),
+///     Err(e) if matches!(e.code, MotdErrorCode::Timeout) => println!("Offline"),
+///     Err(e) => println!("Error: {}", e.message),
+/// }
+/// ```
+///
+/// IPv6 servers are queried the same way, socket of the matching family is bound automatically:
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use mcpe_motd::fetch_unconected_pong_timeout;
+///
+/// let pong = fetch_unconected_pong_timeout("[::1]:19132", Duration::from_secs(1))?;
+///
+/// println!("{}", pong.server_id_string_parsed.motd);
+/// # Ok::<(), mcpe_motd::MotdError>(())
+/// ```
+pub fn fetch_unconected_pong_timeout(addr: impl ToSocketAddrs, timeout: Duration) -> Result<UnconnectedPong> {
+    FetchOptions::default().timeout(timeout).fetch(addr)
+}
+
+/// Same as *fetch_unconected_pong_timeout*, but sends the ping up to `attempts` times (at least once) and returns the first pong.
+/// UDP is lossy, so a single lost packet doesn't turn into an error. For more control use [`FetchOptions::retries`].
+///
+/// # Arguments
+///
+/// * `addr` - address of the target server.
+/// * `attempts` - how many times to try before giving up.
+/// * `timeout` - how long to wait for the response on each attempt.
+///
+/// # Panics
+///
+/// Function can return the same errors as *fetch_unconected_pong*, only after every attempt failed.
+/// Error is the one of the last attempt, its message says how many attempts were made.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use mcpe_motd::fetch_unconected_pong_retry;
+///
+/// let pong = fetch_unconected_pong_retry("127.0.0.1:19132", 3, Duration::from_secs(1))?;
+///
+/// println!("{}", pong.server_id_string_parsed.motd);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn fetch_unconected_pong_retry(addr: impl ToSocketAddrs, attempts: u32, timeout: Duration) -> Result<UnconnectedPong> {
+    FetchOptions::default().timeout(timeout).retries(attempts.saturating_sub(1)).fetch(addr)
+}
+
+/// Same as *fetch_unconected_pong_timeout*, but waits until `deadline` instead of for a duration,
+/// so several operations can share one overall deadline.
+///
+/// # Arguments
+///
+/// * `addr` - address of the target server.
+/// * `deadline` - when to stop waiting for the response.
+///
+/// # Panics
+///
+/// Function can return the same errors as *fetch_unconected_pong*.
+/// If `deadline` has already passed, it returns *MotdErrorCode::Timeout* without sending anything.
+///
+/// # Example
+///
+/// ```
+/// use std::time::{Duration, Instant};
+/// use mcpe_motd::{fetch_unconected_pong_deadline, MotdErrorCode};
+///
+/// let deadline = Instant::now();
+/// std::thread::sleep(Duration::from_millis(1));
+///
+/// let e = fetch_unconected_pong_deadline("127.0.0.1:19132", deadline).unwrap_err();
+/// assert_eq!(e.code, MotdErrorCode::Timeout);
+/// ```
+pub fn fetch_unconected_pong_deadline(addr: impl ToSocketAddrs, deadline: Instant) -> Result<UnconnectedPong> {
+    FetchOptions::default().timeout(deadline.saturating_duration_since(Instant::now())).fetch(addr)
+}
+
+/// Same as *fetch_unconected_pong*, but sends and receives on `socket` the caller already created and configured
+/// (e.g. bound to a specific interface or source port, or reused for many queries).
+/// Socket's read timeout is set to [`DEFAULT_TIMEOUT`], for other options use [`FetchOptions::fetch_with_socket`].
+///
+/// # Arguments
+///
+/// * `socket` - bound socket of the same family as `addr`.
+/// * `addr` - address of the target server.
+///
+/// # Panics
+///
+/// Function can return the same errors as *fetch_unconected_pong*.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::net::UdpSocket;
+/// use mcpe_motd::fetch_unconected_pong_with_socket;
+///
+/// let socket = UdpSocket::bind("0.0.0.0:40000")?;
+///
+/// for addr in ["127.0.0.1:19132", "127.0.0.1:19133"] {
+///     match fetch_unconected_pong_with_socket(&socket, addr) {
+///         Ok(pong) => println!("{}: {}", addr, pong.server_id_string_parsed.motd),
+///         Err(e) => println!("{}: {}", addr, e),
+///     }
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn fetch_unconected_pong_with_socket(socket: &UdpSocket, addr: impl ToSocketAddrs) -> Result<UnconnectedPong> {
+    FetchOptions::default().fetch_with_socket(socket, addr)
+}
+
+/// Returns parsed [server id string](https://wiki.vg/Raknet_Protocol#Unconnected_Pong) or error explaining why it wasn't parsed.
+/// # Arguments
+///
+/// * `addr` - address of the target server.
+///
+/// # Panics
+///
+/// Function can return an error if:
+///  - couldn't send packet to the target server
+///  - couldn't parse response (e.g. invalid unconnected pong packet)
+///
+/// However, it will try to replace invalid data with default ones (e.g. empty port field in server id string will be replaced with [`DEFAULT_PORT`]) until minecraft can process that data.
+/// Obviously, minecraft won't work with empty server id string or *String* instead of *version_protocol field*.
+///
+/// # Example
+///
+/// ```no_run
+/// use::mcpe_motd::fetch_server_id_string;
+///
+/// let server_id_string = fetch_server_id_string("127.0.0.1:19132")?;
+///
+/// // Will print -1 / -1 if server id string is invalid (as well as vanilla minecraft will).
+/// println!("There are {} / {} players on the server.",
+/// server_id_string.player_count,
+/// server_id_string.max_player_count);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn fetch_server_id_string(addr: impl ToSocketAddrs) -> Result<ServerIdStringParsed> {
+    let unconected_pong = fetch_unconected_pong(addr)?;
+
+    Ok(unconected_pong.server_id_string_parsed)
+}
+
+/// Same as *fetch_server_id_string*, but waits for the response only for `timeout` (see *fetch_unconected_pong_timeout*).
+///
+/// # Arguments
+///
+/// * `addr` - address of the target server.
+/// * `timeout` - how long to wait for the response.
+///
+/// # Panics
+///
+/// Function can return the same errors as *fetch_unconected_pong_timeout*.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use mcpe_motd::fetch_server_id_string_timeout;
+///
+/// let server_id_string = fetch_server_id_string_timeout("127.0.0.1:19132", Duration::from_secs(1))?;
+///
+/// println!("{}", server_id_string.motd);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn fetch_server_id_string_timeout(addr: impl ToSocketAddrs, timeout: Duration) -> Result<ServerIdStringParsed> {
+    Ok(fetch_unconected_pong_timeout(addr, timeout)?.server_id_string_parsed)
+}
+
+/// Same as *fetch_server_id_string*, but sends the ping up to `attempts` times (see *fetch_unconected_pong_retry*).
+///
+/// # Arguments
+///
+/// * `addr` - address of the target server.
+/// * `attempts` - how many times to try before giving up.
+/// * `timeout` - how long to wait for the response on each attempt.
+///
+/// # Panics
+///
+/// Function can return the same errors as *fetch_unconected_pong_retry*.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use mcpe_motd::fetch_server_id_string_retry;
+///
+/// let server_id_string = fetch_server_id_string_retry("127.0.0.1:19132", 3, Duration::from_secs(1))?;
+///
+/// println!("{}", server_id_string.motd);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn fetch_server_id_string_retry(addr: impl ToSocketAddrs, attempts: u32, timeout: Duration) -> Result<ServerIdStringParsed> {
+    Ok(fetch_unconected_pong_retry(addr, attempts, timeout)?.server_id_string_parsed)
+}
+
+/// Same as *fetch_server_id_string*, but fetches with `options` (see [`FetchOptions::fetch`]) and returns round-trip time of the pong as well.
+///
+/// # Arguments
+///
+/// * `addr` - address of the target server.
+/// * `options` - timeout, retries and other fetch settings.
+///
+/// # Panics
+///
+/// Function can return the same errors as [`FetchOptions::fetch`].
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use mcpe_motd::{fetch_server_id_string_full, FetchOptions};
+///
+/// let options = FetchOptions::new().timeout(Duration::from_secs(1));
+/// let (server_id_string, latency) = fetch_server_id_string_full("127.0.0.1:19132", &options)?;
+///
+/// println!("{} ({} ms)", server_id_string.motd, latency.as_millis());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn fetch_server_id_string_full(addr: impl ToSocketAddrs, options: &FetchOptions) -> Result<(ServerIdStringParsed, Duration)> {
+    let UnconnectedPong { server_id_string_parsed, latency, .. } = options.fetch(addr)?;
+
+    Ok((server_id_string_parsed, latency))
+}
+
+/// Same as *fetch_unconected_pong*, but `host` may omit the port, [`DEFAULT_PORT`] is used then (see [`resolve_host`]).
+///
+/// # Arguments
+///
+/// * `host` - hostname or ip of the target server, with or without port (e.g. "play.example.com" or "play.example.com:19133").
+///
+/// # Panics
+///
+/// Function can return the same errors as *fetch_unconected_pong* and [`resolve_host`].
+///
+/// # Example
+///
+/// ```no_run
+/// use mcpe_motd::fetch_unconected_pong_host;
+///
+/// let pong = fetch_unconected_pong_host("play.example.com")?;
+///
+/// println!("{}", pong.server_id_string_parsed.motd);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn fetch_unconected_pong_host(host: &str) -> Result<UnconnectedPong> {
+    fetch_unconected_pong(resolve_host(host)?)
+}
+
+/// Resolves `host` into the address ping is sent to (the first one if there are several), using [`DEFAULT_PORT`] if `host` has no port.
+/// IPv6 literals may be given with or without brackets (`::1`, `[::1]` or `[::1]:19133`).
+///
+/// SRV records aren't looked up: Bedrock clients don't use them, so servers are expected to be reachable on the port itself.
+///
+/// # Arguments
+///
+/// * `host` - hostname or ip, with or without port.
+///
+/// # Panics
+///
+/// Function can return an error if `host` couldn't be resolved or didn't resolve to any address.
+///
+/// # Example
+///
+/// ```
+/// use mcpe_motd::{resolve_host, DEFAULT_PORT};
+///
+/// assert_eq!(resolve_host("127.0.0.1")?.port(), DEFAULT_PORT);
+/// assert_eq!(resolve_host("127.0.0.1:19133")?.to_string(), "127.0.0.1:19133");
+/// assert_eq!(resolve_host("::1")?.to_string(), "[::1]:19132");
+/// assert_eq!(resolve_host("[::1]")?.to_string(), "[::1]:19132");
+///
+/// assert!(resolve_host("127.0.0.1:port").is_err());
+///
+/// // `.invalid` names never resolve
+/// let e = resolve_host("mcpe-motd.invalid").unwrap_err();
+/// assert_eq!(e.code, mcpe_motd::MotdErrorCode::CantResolveAddress);
+/// assert!(e.message.contains("mcpe-motd.invalid"));
+/// # Ok::<(), mcpe_motd::MotdError>(())
+/// ```
+pub fn resolve_host(host: &str) -> Result<SocketAddr> {
+    if let Ok(addr) = host.parse::<SocketAddr>() {
+        return Ok(addr);
+    }
+
+    let ip = host.strip_prefix('[').and_then(|h| h.strip_suffix(']')).unwrap_or(host);
+
+    if let Ok(ip) = ip.parse::<IpAddr>() {
+        return Ok(SocketAddr::new(ip, DEFAULT_PORT));
+    }
+
+    // Hostnames can't contain ':', so anything after it is the port
+    let addr = match host.split_once(':') {
+        Some((name, port)) => match port.parse::<u16>() {
+            Ok(port) => (name, port).to_socket_addrs(),
+            Err(_) => return Err(MotdError::new(MotdErrorCode::CantResolveAddress, format!("Invalid port in {}", host))),
+        },
+        None => (host, DEFAULT_PORT).to_socket_addrs(),
+    };
+
+    match addr {
+        Ok(mut addrs) => match addrs.next() {
+            Some(addr) => Ok(addr),
+            None => Err(MotdError::new(MotdErrorCode::CantResolveAddress, format!("{} didn't resolve to any address", host))),
+        },
+        Err(e) => Err(MotdError::new(MotdErrorCode::CantResolveAddress, format!("Couldn't resolve {}: {}", host, e))),
+    }
+}
+
+/// Checks `addr` the same way *fetch_unconected_pong* resolves it, without sending anything, and returns the address ping would be sent to.
+/// Meant for validating user input (e.g. while it's being typed) before the actual fetch.
+/// The port is required, use [`resolve_host`] for input that may omit it.
+///
+/// # Arguments
+///
+/// * `addr` - address of the target server with port (e.g. "127.0.0.1:19132" or "play.example.com:19132").
+///
+/// # Panics
+///
+/// Function can return an error if `addr` couldn't be resolved or didn't resolve to any address.
+///
+/// # Example
+///
+/// ```
+/// use mcpe_motd::{validate_address, MotdErrorCode};
+///
+/// assert_eq!(validate_address("127.0.0.1:19132")?.to_string(), "127.0.0.1:19132");
+///
+/// let e = validate_address("127.0.0.1").unwrap_err();
+/// assert_eq!(e.code, MotdErrorCode::CantResolveAddress);
+/// assert!(e.message.contains("127.0.0.1"));
+/// # Ok::<(), mcpe_motd::MotdError>(())
+/// ```
+pub fn validate_address(addr: &str) -> Result<SocketAddr> {
+    resolve(addr).map_err(|e| MotdError { message: format!("{}: {}", addr, e.message), ..e })
+}
+
+/// Returns any address of the same family as `addr` with a random port (`0.0.0.0:0` or `[::]:0`), so a socket bound to it can reach `addr`.
+fn any_addr(addr: SocketAddr) -> SocketAddr {
+    match addr {
+        SocketAddr::V4(_) => SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0)),
+        SocketAddr::V6(_) => SocketAddr::from((Ipv6Addr::UNSPECIFIED, 0)),
+    }
+}
+
+/// Longest start of `raw` whose length fits the 2-byte length of server id string (cut at a character boundary), with that length.
+fn fit_server_id_string(raw: &str) -> (&str, u16) {
+    if let Ok(len) = u16::try_from(raw.len()) {
+        return (raw, len);
+    }
+
+    let mut end = u16::MAX as usize;
+
+    while !raw.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    (&raw[..end], end as u16)
+}
+
+/// Quotes up to [`ERROR_QUOTE_LEN`] characters of `raw` for an error message, escaping control characters.
+fn quote(raw: &str) -> String {
+    match raw.char_indices().nth(ERROR_QUOTE_LEN) {
+        Some((end, _)) => format!("{:?}...", &raw[..end]),
+        None => format!("{:?}", raw),
+    }
+}
+
+/// Whether `packet` looks like an unconnected pong carrying `magic` (where [`parse_pong`] looks for it), without parsing it.
+fn is_pong(packet: &[u8], magic: &[u8; 16]) -> bool {
+    packet.len() >= 35 && packet[0] == 0x1c && packet[17..packet.len() - 2].windows(16).any(|window| window == magic)
+}
+
+/// Whether `src` is `addr`, treating IPv4-mapped IPv6 addresses (from dual-stack sockets) as IPv4.
+fn same_addr(src: SocketAddr, addr: SocketAddr) -> bool {
+    src.port() == addr.port() && src.ip().to_canonical() == addr.ip().to_canonical()
+}
+
+/// Sets IPv4 type of service or IPv6 traffic class, depending on the family of `addr`.
+#[cfg(feature = "tos")]
+fn set_tos(socket: &UdpSocket, addr: SocketAddr, tos: u8) -> std::io::Result<()> {
+    let socket = socket2::SockRef::from(socket);
+
+    match addr {
+        SocketAddr::V4(_) => socket.set_tos_v4(tos as u32),
+        #[cfg(any(target_os = "linux", target_os = "android", target_os = "macos", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd", target_os = "dragonfly"))]
+        SocketAddr::V6(_) => socket.set_tclass_v6(tos as u32),
+        #[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd", target_os = "dragonfly")))]
+        SocketAddr::V6(_) => Err(std::io::Error::new(ErrorKind::Unsupported, "IPv6 traffic class isn't supported on this platform")),
+    }
+}
+
+/// Resolves `addr` into the address ping is sent to (the first one if there are several).
+fn resolve(addr: impl ToSocketAddrs) -> Result<SocketAddr> {
+    match addr.to_socket_addrs() {
+        Ok(mut addrs) => match addrs.next() {
+            Some(addr) => Ok(addr),
+            None => Err(MotdError::new(MotdErrorCode::CantResolveAddress, String::from("Address didn't resolve to anything"))),
+        },
+        Err(e) => Err(MotdError::new(MotdErrorCode::CantResolveAddress, format!("Couldn't resolve address: {}", e))),
+    }
+}
+
+/// Returns parsed [RakNet unconnected pong packet](https://wiki.vg/Raknet_Protocol#Unconnected_Pong) from raw bytes (e.g. captured with a proxy or from a pcap).
+/// It's the same parser *fetch_unconected_pong* uses, just without the network.
+/// Network-dependent fields (*source_addr*, *raw_fields*) are `None`, *latency* is zero and *received_at* is `UNIX_EPOCH`.
+///
+/// # Arguments
+///
+/// * `response` - whole unconnected pong datagram.
+///
+/// # Panics
+///
+/// Function can return an error if:
+///  - packet is shorter than its header, or its server id string length doesn't match the packet
+///  - packet id isn't 0x1c or there is no magic after the guid
+///  - couldn't parse server id string
+///
+/// # Example
+///
+/// ```
+/// use mcpe_motd::{parse_unconnected_pong, MotdErrorCode, ParseWarning, UnconnectedPong};
+///
+/// let mut bytes = vec![0x1c];
+/// bytes.extend_from_slice(&16u64.to_be_bytes());
+/// // Guid with the high bit set is still positive
+/// bytes.extend_from_slice(&0xf123456789abcdefu64.to_be_bytes());
+/// bytes.extend_from_slice(&[0x00, 0xff, 0xff, 0x00, 0xfe, 0xfe, 0xfe, 0xfe, 0xfd, 0xfd, 0xfd, 0xfd, 0x12, 0x34, 0x56, 0x78]);
+/// let server_id_string = "MCPE;Dedicated Server;615;1.20.30;3;10";
+/// bytes.extend_from_slice(&(server_id_string.len() as u16).to_be_bytes());
+/// bytes.extend_from_slice(server_id_string.as_bytes());
+///
+/// let pong = parse_unconnected_pong(&bytes).unwrap();
+///
+/// assert_eq!(pong.server_guid, 0xf123456789abcdef);
+/// assert_eq!(pong.time_since_start, 16);
+/// assert_eq!(pong.server_id_string_parsed.motd, "Dedicated Server");
+/// assert_eq!(pong.to_bytes(), bytes);
+/// assert_eq!(parse_unconnected_pong(&pong.to_bytes()).unwrap(), pong.clone());
+/// assert_eq!(UnconnectedPong::try_from(&bytes[..]).unwrap(), pong);
+///
+/// // Only 6 fields were sent, the other 6 are defaults
+/// assert_eq!(pong.warnings.len(), 6);
+/// assert!(pong.warnings.contains(&ParseWarning::MissingPortV4));
+/// assert!(!pong.server_id_string_parsed_ok);
+///
+/// // Truncated packet is an error, not a panic
+/// let e = parse_unconnected_pong(&bytes[..20]).unwrap_err();
+/// assert_eq!(e.code, MotdErrorCode::PacketTooShort);
+///
+/// // Even an empty datagram
+/// let e = parse_unconnected_pong(&[]).unwrap_err();
+/// assert_eq!(e.code, MotdErrorCode::PacketTooShort);
+/// assert_eq!(e.message, "Packet is empty");
+///
+/// // Or a lone packet id
+/// let e = parse_unconnected_pong(&[0x1c]).unwrap_err();
+/// assert_eq!(e.code, MotdErrorCode::PacketTooShort);
+///
+/// // And so is a length that is larger than the server id string
+/// let e = parse_unconnected_pong(&bytes[..40]).unwrap_err();
+/// assert_eq!(e.code, MotdErrorCode::LengthMismatch);
+///
+/// // Extra bytes before the magic are skipped
+/// let mut padded = bytes[..17].to_vec();
+/// padded.extend_from_slice(&[0, 0]);
+/// padded.extend_from_slice(&bytes[17..]);
+///
+/// let padded_pong = parse_unconnected_pong(&padded).unwrap();
+/// assert_eq!(padded_pong.server_guid, 0xf123456789abcdef);
+/// assert_eq!(padded_pong.server_id_string_raw, server_id_string);
+///
+/// // But without the magic it isn't an unconnected pong at all
+/// let mut no_magic = bytes.clone();
+/// no_magic[21] = 0;
+/// assert_eq!(parse_unconnected_pong(&no_magic).unwrap_err().code, MotdErrorCode::InvalidMagic);
+///
+/// // Vanilla servers don't send gamemodes outside 0 - 3
+/// let server_id_string = "MCPE;Dedicated Server;615;1.20.30;3;10;1;world;Survival;7;19132;19132";
+/// let mut bytes = bytes[..33].to_vec();
+/// bytes.extend_from_slice(&(server_id_string.len() as u16).to_be_bytes());
+/// bytes.extend_from_slice(server_id_string.as_bytes());
+///
+/// let pong = parse_unconnected_pong(&bytes).unwrap();
+/// assert_eq!(pong.server_id_string_parsed.gamemode_numeric, 7);
+/// assert!(!pong.server_id_string_parsed_ok);
+/// assert_eq!(pong.warnings, [ParseWarning::UnknownGamemode(7)]);
+///
+/// // And so don't they send broken editions
+/// let server_id_string = b"M\xffPE;Dedicated Server;615;1.20.30;3;10;1;world;Survival;1;19132;19132";
+/// let mut bytes = bytes[..33].to_vec();
+/// bytes.extend_from_slice(&(server_id_string.len() as u16).to_be_bytes());
+/// bytes.extend_from_slice(server_id_string);
+///
+/// let pong = parse_unconnected_pong(&bytes).unwrap();
+/// assert_eq!(pong.server_id_string_parsed.edition.as_str(), "M\u{fffd}PE");
+/// assert_eq!(pong.warnings, [ParseWarning::UnknownEdition, ParseWarning::InvalidUtf8]);
+///
+/// // 618 is 1.20.30 (or its hotfixes), so the version name wasn't updated
+/// let server_id_string = "MCPE;Dedicated Server;618;1.20.10;3;10;1;world;Survival;1;19132;19132";
+/// let mut bytes = bytes[..33].to_vec();
+/// bytes.extend_from_slice(&(server_id_string.len() as u16).to_be_bytes());
+/// bytes.extend_from_slice(server_id_string.as_bytes());
+///
+/// let pong = parse_unconnected_pong(&bytes).unwrap();
+/// assert_eq!(pong.warnings, [ParseWarning::VersionMismatch]);
+///
+/// let bytes = [&bytes[..35], b"MCPE;Dedicated Server;618;1.20.32;3;10;1;world;Survival;1;19132;19132"].concat();
+/// assert!(parse_unconnected_pong(&bytes).unwrap().warnings.is_empty());
+///
+/// // Cut in the middle of "é", the partial character is dropped
+/// let server_id_string = "MCPE;Dedicated Server;615;1.20.30;3;10;1;world;Survival;1;19132;19132;Poké".as_bytes();
+/// let server_id_string = &server_id_string[..server_id_string.len() - 1];
+/// let mut bytes = bytes[..33].to_vec();
+/// bytes.extend_from_slice(&(server_id_string.len() as u16).to_be_bytes());
+/// bytes.extend_from_slice(server_id_string);
+///
+/// let pong = parse_unconnected_pong(&bytes).unwrap();
+/// assert_eq!(pong.server_id_string_parsed.extra_fields, ["Pok"]);
+/// assert_eq!(pong.warnings, [ParseWarning::TruncatedString]);
+///
+/// // Even after an invalid byte earlier in the string
+/// let server_id_string = b"MCPE;Dedicated Server;615;1.20.30;3;10;1;world;Survival;1;19132;19132;\xff;Pok\xc3";
+/// let mut bytes = bytes[..33].to_vec();
+/// bytes.extend_from_slice(&(server_id_string.len() as u16).to_be_bytes());
+/// bytes.extend_from_slice(server_id_string);
+///
+/// let pong = parse_unconnected_pong(&bytes).unwrap();
+/// assert_eq!(pong.server_id_string_parsed.extra_fields, ["\u{fffd}", "Pok"]);
+/// assert_eq!(pong.warnings, [ParseWarning::InvalidUtf8, ParseWarning::TruncatedString]);
+///
+/// // Server id string that couldn't be parsed is kept in the error
+/// let server_id_string = "MCPE;Dedicated Server;not a number;1.20.30";
+/// let mut bytes = bytes[..33].to_vec();
+/// bytes.extend_from_slice(&(server_id_string.len() as u16).to_be_bytes());
+/// bytes.extend_from_slice(server_id_string.as_bytes());
+///
+/// let e = parse_unconnected_pong(&bytes).unwrap_err();
+/// assert_eq!(e.code, MotdErrorCode::CantParseProtocolVersion);
+/// assert_eq!(e.raw.as_deref(), Some(server_id_string));
+///
+/// // Length is unsigned, 0x8000 is 32768 bytes rather than a negative number
+/// let server_id_string = format!("MCPE;{};615;1.20.30", "a".repeat(0x8000 - 17));
+/// let mut bytes = bytes[..33].to_vec();
+/// bytes.extend_from_slice(&[0x80, 0x00]);
+/// bytes.extend_from_slice(server_id_string.as_bytes());
+///
+/// let pong = parse_unconnected_pong(&bytes).unwrap();
+/// assert_eq!(pong.server_id_string_len, 0x8000);
+/// assert_eq!(pong.server_id_string_raw.len(), 0x8000);
+/// assert_eq!(pong.raw_packet_len, 35 + 0x8000);
+///
+/// let e = parse_unconnected_pong(&bytes[..35 + 0x7fff]).unwrap_err();
+/// assert_eq!(e.code, MotdErrorCode::LengthMismatch);
+///
+/// // Corrupt length larger than any packet is an error too
+/// let mut corrupt = bytes.clone();
+/// corrupt[33..35].copy_from_slice(&[0xff, 0xff]);
+/// let e = parse_unconnected_pong(&corrupt).unwrap_err();
+/// assert_eq!(e.code, MotdErrorCode::LengthMismatch);
+/// assert_eq!(e.message, "Packet has 32768 bytes of server id string, but its length says 65535");
+/// ```
+pub fn parse_unconnected_pong(response: &[u8]) -> Result<UnconnectedPong> {
+    parse_pong(response, &FetchOptions::default())
+}
+
+/// Parses unconnected pong, decoding server id string lossily unless `strict_utf8` is set.
+/// Only the parsing settings of `options` are used (*strict_utf8*, *infer_string_length*, *lenient* and *magic*).
+fn parse_pong(response: &[u8], options: &FetchOptions) -> Result<UnconnectedPong> {
+    let expected_magic = &options.magic;
+
+    // Id, time, server guid, magic and server id string length
+    const HEADER_LEN: usize = 35;
+
+    // Not even the packet id (e.g. an empty datagram some firewalls answer with)
+    if response.is_empty() {
+        return Err(MotdError::new(MotdErrorCode::PacketTooShort, String::from("Packet is empty")));
+    }
+
+    if response.len() < HEADER_LEN {
+        return Err(MotdError::new(MotdErrorCode::PacketTooShort, format!("Packet is {} bytes long, but unconnected pong header takes {}", response.len(), HEADER_LEN)));
+    }
+
+    // Packet id (0x1c) - 1 byte
+    let id = response[0];
+
+    if id != 0x1c {
+        return Err(MotdError::new(MotdErrorCode::UnexpectedPacketId, format!("Expected unconnected pong (0x1c), got packet id {:#04x}", id)));
+    }
+
+    // Time since start in ms - 8 bytes
+    let time_since_start: u64 = (response[8] as u64) |
+        (response[7] as u64) << 8 |
+        (response[6] as u64) << 16 |
+        (response[5] as u64) << 24 |
+        (response[4] as u64) << 32 |
+        (response[3] as u64) << 40 |
+        (response[2] as u64) << 48 |
+        (response[1] as u64) << 56;
+
+    // Server GUID - 8 bytes
+    let server_guid: u64 = (response[16] as u64) |
+        (response[15] as u64) << 8 |
+        (response[14] as u64) << 16 |
+        (response[13] as u64) << 24 |
+        (response[12] as u64) << 32 |
+        (response[11] as u64) << 40 |
+        (response[10] as u64) << 48 |
+        (response[9] as u64) << 56;
+
+    // Magic - 16 bytes, normally right after the guid, but some proxies put extra bytes before it.
+    // Everything after it is read relative to where it was found (leaving room for the length).
+    let mut magic = [0; 16];
+    magic.copy_from_slice(&response[17..33]);
+
+    let magic_offset = if magic == *expected_magic {
+        17
+    } else {
+        match response[17..response.len() - 2].windows(16).position(|window| window == expected_magic) {
+            Some(position) => 17 + position,
+            None => { return Err(MotdError::new(MotdErrorCode::InvalidMagic, format!("Expected magic {:02x?}, got {:02x?}", expected_magic, magic))); }
+        }
+    };
+
+    if magic_offset != 17 {
+        debug!("found magic at offset {} instead of 17", magic_offset);
+        magic = *expected_magic;
+    }
+
+    // Server id string length - 2 bytes, unsigned
+    let mut server_id_string_len = (response[magic_offset + 17] as u16) |
+        (response[magic_offset + 16] as u16) << 8;
+
+    let server_id_string_start = magic_offset + 18;
+    let server_id_string_end = server_id_string_start.saturating_add(server_id_string_len as usize);
+
+    // Server id string - <server_id_string_len> bytes.
+    // Length comes straight from the packet, so it's checked before slicing
+    let server_id_string_bytes = if server_id_string_end <= response.len() {
+        &response[server_id_string_start..server_id_string_end]
+    } else if options.infer_string_length {
+        // No length at all, server id string is everything after the magic
+        let bytes = &response[magic_offset + 16..];
+        debug!("inferred server id string length: length_field={} bytes={}", server_id_string_len, bytes.len());
+
+        server_id_string_len = u16::try_from(bytes.len()).unwrap_or(u16::MAX);
+        bytes
+    } else {
+        return Err(MotdError::new(MotdErrorCode::LengthMismatch, format!("Packet has {} bytes of server id string, but its length says {}", response.len() - server_id_string_start, server_id_string_len)));
+    };
+
+    // Strings cut by the server can end in the middle of a character, it's dropped rather than turned into `�`.
+    // Only the last character is checked, invalid bytes before it don't hide the cut
+    let last_char_start = server_id_string_bytes.iter().rposition(|byte| byte & 0xc0 != 0x80).filter(|&start| start + 4 > server_id_string_bytes.len());
+    let truncated = last_char_start.is_some_and(|start| std::str::from_utf8(&server_id_string_bytes[start..]).is_err_and(|e| e.error_len().is_none()));
+    let server_id_string_bytes = match last_char_start {
+        Some(start) if truncated => &server_id_string_bytes[..start],
+        _ => server_id_string_bytes,
+    };
+
+    let server_id_string = if options.strict_utf8 {
+        match std::str::from_utf8(server_id_string_bytes) {
+            Ok(v) => v.to_string(),
+            Err(e) => {
+                return Err(MotdError { raw: Some(String::from_utf8_lossy(server_id_string_bytes).to_string()), ..MotdError::new(MotdErrorCode::InvalidUtf8, format!("Server id string isn't valid UTF-8: {}", e)) });
+            }
+        }
+    } else {
+        String::from_utf8_lossy(server_id_string_bytes).to_string()
+    };
+
+    let valid_utf8 = std::str::from_utf8(server_id_string_bytes).is_ok();
+
+    let (server_id_string_parsed, mut warnings) = match parse_fields(&server_id_string, options.lenient) {
+        Ok(v) => v,
+        Err(mut e) => {
+            // Keep what server sent, so it can be seen what couldn't be parsed
+            e.raw = Some(server_id_string);
+            return Err(e);
+        }
+    };
+
+    if !valid_utf8 {
+        warnings.push(ParseWarning::InvalidUtf8);
+    }
+
+    if truncated {
+        warnings.push(ParseWarning::TruncatedString);
+    }
+
+    if !warnings.is_empty() {
+        debug!("filled default fields: fields={} raw={:?}", server_id_string_parsed.field_count, server_id_string);
+    }
+
+    Ok(UnconnectedPong {
+        id,
+        time_since_start,
+        server_guid,
+        magic,
+        server_id_string_len,
+        raw_packet_len: response.len(),
+        server_id_string_raw: server_id_string,
+        server_id_string_parsed_ok: warnings.is_empty(),
+        warnings,
+        server_id_string_parsed,
+        raw_fields: None,
+        source_addr: None,
+        latency: Duration::ZERO,
+        received_at: UNIX_EPOCH,
+    })
+}
+
+/// Returns parsed [server id string](https://wiki.vg/Raknet_Protocol#Unconnected_Pong) or error explaining why it wasn't parsed.
+/// Unlike *fetch_server_id_string*, it doesn't touch the network, so it can be used to parse strings from logs or other sources.
+///
+/// # Arguments
+///
+/// * `raw` - raw server id string (e.g. `MCPE;Dedicated Server;615;1.20.30;0;10;...`).
+///
+/// # Panics
+///
+/// Function can return an error if server id string is invalid (e.g. has fewer than 4 fields).
+///
+/// Network functions parse server id string with exactly the same code, so missing optional fields are replaced with the same default ones
+/// (the ones of `ServerIdStringParsed::default()`): -1 player counts, empty server unique id and level name, "Survival" (0) gamemode and [`DEFAULT_PORT`] ports.
+///
+/// # Example
+///
+/// ```
+/// use mcpe_motd::{parse_server_id_string, Edition, Gamemode, MotdErrorCode, ServerIdStringParsed};
+///
+/// let parsed = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30;3;10").unwrap();
+///
+/// assert_eq!(parsed.motd, "Dedicated Server");
+/// assert_eq!(parsed.protocol_version, 615);
+/// assert_eq!(parsed.player_count, 3);
+/// assert_eq!(parsed.max_player_count, 10);
+/// assert_eq!(parsed.level_name, "");
+/// assert_eq!(parsed.gamemode, "Survival");
+/// assert_eq!(parsed.gamemode_numeric, 0);
+/// assert_eq!(parsed.port_v4, 19132);
+/// assert_eq!(parsed.port_v6, 19132);
+/// assert!(parsed.extra_fields.is_empty());
+/// assert_eq!(parsed.gamemode_enum(), Gamemode::Survival);
+///
+/// let parsed = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30").unwrap();
+/// assert_eq!((parsed.player_count, parsed.max_player_count), (-1, -1));
+///
+/// // Everything but the required fields is the default
+/// let default = ServerIdStringParsed { edition: Edition::Mcpe, motd: "Dedicated Server".into(), protocol_version: 615, version_name: "1.20.30".into(), field_count: 4, ..Default::default() };
+/// assert_eq!(parsed, default);
+///
+/// // Fields after port_v6 are kept as they are
+/// let parsed = parse_server_id_string("MCPE;Nukkit;615;1.20.30;0;10;1;world;Survival;1;19132;19133;plugin;data").unwrap();
+/// assert_eq!(parsed.extra_fields, ["plugin", "data"]);
+/// assert_eq!(parsed.gamemode_enum(), Gamemode::Creative);
+/// assert_eq!(parsed.server_unique_id_numeric(), Some(1));
+///
+/// let parsed = parse_server_id_string("MCPE;Nukkit;615;1.20.30;0;10;not a number").unwrap();
+/// assert_eq!(parsed.server_unique_id_numeric(), None);
+///
+/// // Empty fields are kept in place
+/// let parsed = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30;3;10;13253860892328930865;;Creative;1;19132;19133;").unwrap();
+/// assert_eq!(parsed.level_name, "");
+/// assert_eq!(parsed.gamemode, "Creative");
+/// assert_eq!(parsed.port_v6, 19133);
+/// assert!(parsed.extra_fields.is_empty());
+///
+/// // Whitespace around numbers is ignored, string fields are kept as they are
+/// let parsed = parse_server_id_string("MCPE; Dedicated Server ; 615 ;1.20.30; 3;10 ;1;world;Survival; 1;19132 ; 19133").unwrap();
+/// assert_eq!(parsed.motd, " Dedicated Server ");
+/// assert_eq!((parsed.protocol_version, parsed.player_count, parsed.max_player_count), (615, 3, 10));
+/// assert_eq!((parsed.gamemode_numeric, parsed.port_v4, parsed.port_v6), (1, 19132, 19133));
+///
+/// // Doubled `;` is an empty field, not a missing one
+/// let parsed = parse_server_id_string("MCPE;;615;1.20.30;;10").unwrap();
+/// assert_eq!(parsed.motd, "");
+/// assert_eq!((parsed.player_count, parsed.max_player_count), (-1, 10));
+/// assert_eq!(parsed.field_count, 6);
+///
+/// // Trailing `;` doesn't count as a field
+/// let parsed = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30;").unwrap();
+/// assert_eq!(parsed.version_name, "1.20.30");
+/// assert_eq!(parsed.field_count, 4);
+///
+/// let e = parse_server_id_string("MCPE;Dedicated Server;615;").unwrap_err();
+/// assert!(matches!(e.code, MotdErrorCode::ServerIdStringTooSmall));
+///
+/// // Leading `;` shifts every field, so protocol_version isn't a number anymore
+/// let e = parse_server_id_string(";MCPE;Dedicated Server;615;1.20.30").unwrap_err();
+/// assert!(matches!(e.code, MotdErrorCode::CantParseProtocolVersion));
+///
+/// // Protocol versions are unsigned, so they don't run out at 32767
+/// assert_eq!(parse_server_id_string("MCPE;Dedicated Server;700;1.21.30").unwrap().protocol_version, 700);
+/// assert_eq!(parse_server_id_string("MCPE;Dedicated Server;40000;9.0.0").unwrap().protocol_version, 40000);
+/// assert!(parse_server_id_string("MCPE;Dedicated Server;-1;1.20.30").is_err());
+///
+/// let e = parse_server_id_string("MCPE;Dedicated Server").unwrap_err();
+/// assert!(matches!(e.code, MotdErrorCode::ServerIdStringTooSmall));
+///
+/// // Message shows what was received instead
+/// let e = parse_server_id_string("HTTP/1.1 400 Bad Request\r\n").unwrap_err();
+/// assert_eq!(e.message, r#"Server id string has less than 4 required fields: "HTTP/1.1 400 Bad Request\r\n""#);
+/// ```
+pub fn parse_server_id_string(raw: &str) -> Result<ServerIdStringParsed> {
+    match parse_fields(raw, false) {
+        Ok((parsed, _)) => Ok(parsed),
+        Err(e) => Err(e),
+    }
+}
+
+/// Splits server id string on `;` exactly as it is, without interpreting any field: empty fields (including the one after a trailing `;`) are kept.
+/// It's the same vector [`FetchOptions::raw_fields`] attaches to a pong, for server software whose fields mean something else than vanilla ones.
+///
+/// # Example
+///
+/// ```
+/// use mcpe_motd::split_server_id_string;
+///
+/// assert_eq!(split_server_id_string("MCPE;Dedicated Server;615;1.20.30;;10;"), ["MCPE", "Dedicated Server", "615", "1.20.30", "", "10", ""]);
+/// assert_eq!(split_server_id_string("garbage"), ["garbage"]);
+/// ```
+pub fn split_server_id_string(raw: &str) -> Vec<String> {
+    raw.split(';').map(|s| s.to_string()).collect()
+}
+
+/// Returns only the 4 required fields of [server id string](https://wiki.vg/Raknet_Protocol#Unconnected_Pong).
+/// It stops splitting after *version_name*, so it's cheaper than *parse_server_id_string* when the rest of the fields isn't needed (e.g. in big scans).
+///
+/// # Panics
+///
+/// Function can return an error if server id string has fewer than 4 fields or *protocol_version* isn't a valid number.
+///
+/// # Example
+///
+/// ```
+/// use mcpe_motd::{parse_server_id_string_summary, Edition};
+///
+/// let summary = parse_server_id_string_summary("MCPE;Dedicated Server;615;1.20.30;3;10;13253860892328930865").unwrap();
+///
+/// assert_eq!(summary.edition, Edition::Mcpe);
+/// assert_eq!(summary.version_name, "1.20.30");
+/// ```
+pub fn parse_server_id_string_summary(raw: &str) -> Result<ServerIdStringSummary> {
+    let mut fields = raw.split(';');
+
+    let (edition, motd, protocol_version, version_name) = match (fields.next(), fields.next(), fields.next(), fields.next()) {
+        (Some(edition), Some(motd), Some(protocol_version), Some(version_name)) => (edition, motd, protocol_version, version_name),
+        _ => {
+            return Err(MotdError::new(MotdErrorCode::ServerIdStringTooSmall, format!("Server id string has less than 4 required fields: {}", quote(raw))));
+        }
+    };
+
+    Ok(ServerIdStringSummary {
+        edition: Edition::from(edition),
+        motd: motd.to_string(),
+        protocol_version: match protocol_version.trim().parse() {
+            Ok(v) => v,
+            Err(_) => {
+                return Err(MotdError::new(MotdErrorCode::CantParseProtocolVersion, String::from("Couldn't parse protocol_version field from server id string")));
+            }
+        },
+        version_name: version_name.to_string(),
+    })
+}
+
+/// Returns name of the first optional field parser would fill with its default value (missing one, or empty number).
+fn first_defaulted_field(server_id_string: &str) -> Option<&'static str> {
+    // Name and whether it's a number, in the order of server id string
+    const OPTIONAL_FIELDS: [(&str, bool); 8] = [
+        ("player_count", true),
+        ("max_player_count", true),
+        ("server_unique_id", false),
+        ("level_name", false),
+        ("gamemode", false),
+        ("gamemode_numeric", true),
+        ("port_v4", true),
+        ("port_v6", true),
+    ];
+
+    let mut fields = server_id_string.strip_suffix(';').unwrap_or(server_id_string).split(';').skip(4);
+
+    for (name, numeric) in OPTIONAL_FIELDS {
+        match fields.next() {
+            None => return Some(name),
+            Some(field) if numeric && field.trim().is_empty() => return Some(name),
+            _ => (),
+        }
+    }
+
+    None
+}
+
+/// Parses server id string, also returning what wasn't as expected (e.g. default fields that were added).
+/// With `lenient` unparsable optional numbers are replaced with defaults too, instead of being an error.
+fn parse_fields(server_id_string: &str, lenient: bool) -> Result<(ServerIdStringParsed, Vec<ParseWarning>)> {
+    let mut warnings = Vec::new();
+
+    let fields = borrowed::split_fields(server_id_string, lenient, |warning| warnings.push(warning))?;
+    let parsed = fields.parsed;
+    let extra_fields: Vec<String> = fields.extra_fields.map(|s| s.to_string()).collect();
+
+    let mut server_id_string_parsed = ServerIdStringParsed {
+        edition: Edition::from(parsed.edition),
+        motd: parsed.motd.to_string(),
+        protocol_version: parsed.protocol_version,
+        version_name: parsed.version_name.to_string(),
+        player_count: parsed.player_count,
+        max_player_count: parsed.max_player_count,
+        server_unique_id: parsed.server_unique_id.to_string(),
+        level_name: parsed.level_name.to_string(),
+        gamemode: parsed.gamemode.to_string(),
+        gamemode_numeric: parsed.gamemode_numeric,
+        port_v4: parsed.port_v4,
+        port_v6: parsed.port_v6,
+        field_count: fields.known_count + extra_fields.len(),
+        extra_fields,
+        sent_fields: FieldPresence::default(),
+    };
+
+    // Every defaulted field has a warning
+    let sent = |missing: ParseWarning, invalid: Option<ParseWarning>| !warnings.contains(&missing) && invalid.is_none_or(|invalid| !warnings.contains(&invalid));
+    server_id_string_parsed.sent_fields = FieldPresence {
+        player_count: sent(ParseWarning::MissingPlayerCount, Some(ParseWarning::InvalidPlayerCount)),
+        max_player_count: sent(ParseWarning::MissingMaxPlayerCount, Some(ParseWarning::InvalidMaxPlayerCount)),
+        server_unique_id: sent(ParseWarning::MissingServerUniqueId, None),
+        level_name: sent(ParseWarning::MissingLevelName, None),
+        gamemode: sent(ParseWarning::MissingGamemode, None),
+        gamemode_numeric: sent(ParseWarning::MissingGamemodeNumeric, Some(ParseWarning::InvalidGamemodeNumeric)),
+        port_v4: sent(ParseWarning::MissingPortV4, Some(ParseWarning::InvalidPortV4)),
+        port_v6: sent(ParseWarning::MissingPortV6, Some(ParseWarning::InvalidPortV6)),
+    };
+
+    if let Edition::Other(_) = server_id_string_parsed.edition {
+        warnings.push(ParseWarning::UnknownEdition);
+    }
+
+    if versions::version_mismatch(server_id_string_parsed.protocol_version, &server_id_string_parsed.version_name) {
+        warnings.push(ParseWarning::VersionMismatch);
+    }
+
+    // Vanilla only has gamemodes 0 - 3
+    if let Gamemode::Unknown(numeric) = server_id_string_parsed.gamemode_enum() {
+        warnings.push(ParseWarning::UnknownGamemode(numeric));
+    }
+
+    Ok((server_id_string_parsed, warnings))
+}
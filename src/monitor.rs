@@ -0,0 +1,382 @@
+//! Watching servers over time.
+
+use std::net::{ToSocketAddrs, UdpSocket};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::{any_addr, resolve, FetchOptions, MotdError, MotdErrorCode, Result, UnconnectedPong};
+
+/// Whether server answers to pings.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerStatus {
+    /// Server answers.
+    Online,
+    /// Server doesn't answer.
+    Offline,
+}
+
+/// Whether `addr` answers a single ping with a valid pong within `timeout`.
+/// Any error (including a timeout) means `false`, for the reason use [`fetch_unconected_pong_timeout`](crate::fetch_unconected_pong_timeout).
+///
+/// # Arguments
+///
+/// * `addr` - address of the target server.
+/// * `timeout` - how long to wait for the pong.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use mcpe_motd::is_online;
+///
+/// // Nothing listens there
+/// assert!(!is_online("127.0.0.1:9", Duration::from_millis(100)));
+/// ```
+pub fn is_online(addr: impl ToSocketAddrs, timeout: Duration) -> bool {
+    FetchOptions::default().timeout(timeout).fetch(addr).is_ok()
+}
+
+/// Fetches `addr` and returns one line describing it for CLI output, never an error. Format is stable:
+///
+///  - `✓ <addr>  <version>  <players>  <latency>ms  <motd>` if server answered (players like [`ServerIdStringParsed::players_display`](crate::ServerIdStringParsed::players_display),
+///    first line of motd without formatting codes), e.g. `✓ play.example.com  1.20.30  3/20  45ms  Dedicated Server`
+///  - `✗ <addr>  offline` if anything failed
+///
+/// # Arguments
+///
+/// * `addr` - address of the target server.
+/// * `timeout` - how long to wait for the pong.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use mcpe_motd::status_line;
+///
+/// assert_eq!(status_line("127.0.0.1:9", Duration::from_millis(100)), "✗ 127.0.0.1:9  offline");
+/// ```
+pub fn status_line(addr: &str, timeout: Duration) -> String {
+    match FetchOptions::default().timeout(timeout).fetch(addr) {
+        Ok(pong) => {
+            let parsed = &pong.server_id_string_parsed;
+            let motd = parsed.motd_plain();
+
+            format!(
+                "✓ {}  {}  {}  {}ms  {}",
+                addr,
+                parsed.version_name,
+                parsed.players_display(),
+                pong.latency.as_millis(),
+                motd.lines().next().unwrap_or(""),
+            )
+        }
+        Err(_) => format!("✗ {}  offline", addr),
+    }
+}
+
+/// Status change reported by [`StatusMonitor`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusTransition {
+    /// Previous status.
+    pub from: ServerStatus,
+    /// New status.
+    pub to: ServerStatus,
+    /// How long server was in the previous status (counting from the first result that disagreed with it).
+    pub previous_duration: Duration,
+}
+
+/// Turns a stream of fetch results into *Online → Offline* and *Offline → Online* transitions.
+/// Status changes only after `threshold` consecutive results agree, so a single dropped ping doesn't make it flap.
+///
+/// Status is unknown until the first `threshold` results agree, establishing it doesn't produce a transition.
+///
+/// # Example
+///
+/// ```
+/// use std::time::{Duration, Instant};
+/// use mcpe_motd::{ServerStatus, StatusMonitor};
+///
+/// let mut monitor = StatusMonitor::new(2);
+/// let start = Instant::now();
+///
+/// assert_eq!(monitor.record_at(true, start), None);
+/// assert_eq!(monitor.record_at(true, start), None);
+/// assert_eq!(monitor.status(), Some(ServerStatus::Online));
+///
+/// // One lost ping isn't enough
+/// assert_eq!(monitor.record_at(false, start + Duration::from_secs(10)), None);
+/// assert_eq!(monitor.record_at(true, start + Duration::from_secs(20)), None);
+///
+/// assert_eq!(monitor.record_at(false, start + Duration::from_secs(30)), None);
+/// let transition = monitor.record_at(false, start + Duration::from_secs(40)).unwrap();
+///
+/// assert_eq!(transition.from, ServerStatus::Online);
+/// assert_eq!(transition.to, ServerStatus::Offline);
+/// assert_eq!(transition.previous_duration, Duration::from_secs(30));
+/// ```
+#[derive(Debug, Clone)]
+pub struct StatusMonitor {
+    threshold: u32,
+    status: Option<ServerStatus>,
+    since: Option<Instant>,
+    streak: u32,
+    streak_start: Option<(ServerStatus, Instant)>,
+}
+
+impl StatusMonitor {
+    /// Creates monitor that changes status after `threshold` consecutive results (at least 1).
+    pub fn new(threshold: u32) -> Self {
+        StatusMonitor {
+            threshold: threshold.max(1),
+            status: None,
+            since: None,
+            streak: 0,
+            streak_start: None,
+        }
+    }
+
+    /// Current status (`None` until enough results were recorded).
+    pub fn status(&self) -> Option<ServerStatus> {
+        self.status
+    }
+
+    /// Records result of a fetch made right now, see [`StatusMonitor::record_at`].
+    pub fn record(&mut self, online: bool) -> Option<StatusTransition> {
+        self.record_at(online, Instant::now())
+    }
+
+    /// Records whether fetch made at `at` succeeded (e.g. `result.is_ok()`).
+    /// Returns transition if status changed because of it.
+    pub fn record_at(&mut self, online: bool, at: Instant) -> Option<StatusTransition> {
+        let observed = if online { ServerStatus::Online } else { ServerStatus::Offline };
+
+        if self.status == Some(observed) {
+            self.streak = 0;
+            self.streak_start = None;
+            return None;
+        }
+
+        // Status actually changed when the first disagreeing result was seen
+        let changed_at = match self.streak_start {
+            Some((status, since)) if status == observed => since,
+            _ => {
+                self.streak = 0;
+                self.streak_start = Some((observed, at));
+                at
+            }
+        };
+
+        self.streak += 1;
+
+        if self.streak < self.threshold {
+            return None;
+        }
+
+        self.streak = 0;
+        self.streak_start = None;
+
+        let transition = match (self.status, self.since) {
+            (Some(from), Some(since)) => Some(StatusTransition {
+                from,
+                to: observed,
+                previous_duration: changed_at.saturating_duration_since(since),
+            }),
+            _ => None,
+        };
+
+        self.status = Some(observed);
+        self.since = Some(changed_at);
+
+        transition
+    }
+}
+
+/// Endless iterator fetching `addr` every `interval` (first fetch happens right away).
+/// Every item is a separate result, so a failed fetch doesn't end polling. Interval is counted from the start of the previous fetch
+/// and sleeping happens only when the next item is requested, so `.take(n)` returns right after the n-th fetch.
+///
+/// One iterator binds one socket on the first fetch and reuses it for every next one, so a long-running poll doesn't use up ephemeral ports.
+/// Socket is bound again only after it failed to send or receive. To control the socket yourself use [`poll_with_socket`].
+///
+/// Pongs are matched to pings with [`FetchOptions::correlate`], so a late pong of the previous poll isn't taken for the current one
+/// (*time_since_start* of the pongs is the nonce because of that).
+///
+/// # Arguments
+///
+/// * `addr` - address of the target server.
+/// * `interval` - time between fetches.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use mcpe_motd::poll;
+///
+/// for result in poll("127.0.0.1:19132", Duration::from_secs(60)).take(60) {
+///     match result {
+///         Ok(pong) => println!("{} players", pong.server_id_string_parsed.player_count),
+///         Err(e) => println!("{}", e),
+///     }
+/// }
+/// ```
+pub fn poll<A: ToSocketAddrs>(addr: A, interval: Duration) -> impl Iterator<Item = Result<UnconnectedPong>> {
+    let options = FetchOptions::default().correlate(true);
+    let mut socket: Option<UdpSocket> = None;
+
+    every(interval, move || {
+        let addr = resolve(&addr)?;
+
+        let sock = match &socket {
+            Some(sock) => sock,
+            None => {
+                let bind_addr = any_addr(addr);
+
+                match UdpSocket::bind(bind_addr) {
+                    Ok(sock) => socket.insert(sock),
+                    Err(e) => { return Err(MotdError::new(MotdErrorCode::CantBind, format!("Couldn't bind to {}: {}", bind_addr, e))); }
+                }
+            }
+        };
+
+        let result = options.fetch_with_socket(sock, addr);
+
+        // Timeouts and bad packets are the server's fault, socket errors aren't
+        if let Err(e) = &result {
+            if matches!(e.code, MotdErrorCode::CantSendTo | MotdErrorCode::CantReceive) {
+                socket = None;
+            }
+        }
+
+        result
+    })
+}
+
+/// Same as [`poll`], but every fetch is made on `socket` (bound by the caller, of the same family as `addr`), which is never rebound.
+///
+/// # Arguments
+///
+/// * `socket` - bound socket of the same family as `addr`.
+/// * `addr` - address of the target server.
+/// * `interval` - time between fetches.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::net::UdpSocket;
+/// use std::time::Duration;
+/// use mcpe_motd::poll_with_socket;
+///
+/// let socket = UdpSocket::bind("0.0.0.0:40000")?;
+///
+/// for result in poll_with_socket(&socket, "127.0.0.1:19132", Duration::from_secs(60)).take(60) {
+///     match result {
+///         Ok(pong) => println!("{} players", pong.server_id_string_parsed.player_count),
+///         Err(e) => println!("{}", e),
+///     }
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn poll_with_socket<'a, A: ToSocketAddrs + 'a>(socket: &'a UdpSocket, addr: A, interval: Duration) -> impl Iterator<Item = Result<UnconnectedPong>> + 'a {
+    let options = FetchOptions::default().correlate(true);
+
+    every(interval, move || options.fetch_with_socket(socket, &addr))
+}
+
+/// Summary of the polls made by [`monitor`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowStats {
+    /// How many polls were made.
+    pub polls: u32,
+    /// How many of them got a valid pong.
+    pub successful: u32,
+    /// Successful polls in percents (0.0 - 100.0), 0.0 if nothing succeeded.
+    pub uptime_pct: f32,
+    /// Lowest player count (`None` if no successful poll had one, counts the server didn't send are skipped).
+    pub min_players: Option<i32>,
+    /// Highest player count.
+    pub max_players: Option<i32>,
+    /// Mean player count.
+    pub avg_players: Option<f32>,
+}
+
+/// Polls `addr` every `interval` (see [`poll`]) for `window` and summarizes the results, e.g. for a status page.
+/// At least one poll is made, the last one starts before `window` ends.
+///
+/// # Arguments
+///
+/// * `addr` - address of the target server.
+/// * `interval` - time between polls.
+/// * `window` - how long to poll for.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use mcpe_motd::monitor;
+///
+/// let stats = monitor("127.0.0.1:19132", Duration::from_secs(60), Duration::from_secs(3600));
+///
+/// println!("uptime {:.1}%, {:?} players on average", stats.uptime_pct, stats.avg_players);
+/// ```
+pub fn monitor<A: ToSocketAddrs>(addr: A, interval: Duration, window: Duration) -> WindowStats {
+    let deadline = Instant::now() + window;
+
+    let mut results = poll(addr, interval);
+
+    let mut polls = 0;
+    let mut successful = 0;
+    let mut players = Vec::new();
+
+    // When the previous poll started, the next one starts an interval later (or right away if it took longer)
+    let mut last_start: Option<Instant> = None;
+
+    loop {
+        let starts_at = match last_start {
+            Some(last) if last + interval >= deadline => break,
+            Some(last) => (last + interval).max(Instant::now()),
+            None => Instant::now(),
+        };
+
+        last_start = Some(starts_at);
+
+        // Poll iterator never ends
+        if let Some(result) = results.next() {
+            polls += 1;
+
+            if let Ok(pong) = result {
+                successful += 1;
+
+                if pong.server_id_string_parsed.player_count >= 0 {
+                    players.push(pong.server_id_string_parsed.player_count);
+                }
+            }
+        }
+    }
+
+    WindowStats {
+        polls,
+        successful,
+        uptime_pct: if polls == 0 { 0.0 } else { successful as f32 * 100.0 / polls as f32 },
+        min_players: players.iter().min().copied(),
+        max_players: players.iter().max().copied(),
+        avg_players: if players.is_empty() { None } else { Some(players.iter().map(|&count| count as f32).sum::<f32>() / players.len() as f32) },
+    }
+}
+
+/// Endless iterator calling `fetch` every `interval`, see [`poll`].
+fn every<F: FnMut() -> Result<UnconnectedPong>>(interval: Duration, mut fetch: F) -> impl Iterator<Item = Result<UnconnectedPong>> {
+    let mut next_at: Option<Instant> = None;
+
+    std::iter::from_fn(move || {
+        if let Some(at) = next_at {
+            thread::sleep(at.saturating_duration_since(Instant::now()));
+        }
+
+        next_at = Some(Instant::now() + interval);
+
+        Some(fetch())
+    })
+}
@@ -0,0 +1,184 @@
+//! Measuring latency and packet loss.
+
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use std::time::Duration;
+
+use crate::{any_addr, resolve, FetchOptions, MotdError, MotdErrorCode, Result, DEFAULT_BUFFER_SIZE, DEFAULT_TIMEOUT};
+
+/// Connection quality reported by [`ping_quality`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PingQuality {
+    /// How many pings were sent.
+    pub sent: u32,
+    /// How many pongs were received in time.
+    pub received: u32,
+    /// Lost pings in percents (0.0 - 100.0).
+    pub loss_pct: f32,
+    /// Mean round-trip time of received pongs (`None` if nothing was received).
+    pub mean_latency: Option<Duration>,
+}
+
+/// Round-trip time statistics reported by [`ping_stats`], durations are `None` if nothing was received.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LatencyStats {
+    /// Fastest round-trip time.
+    pub min: Option<Duration>,
+    /// Slowest round-trip time.
+    pub max: Option<Duration>,
+    /// Mean round-trip time.
+    pub avg: Option<Duration>,
+    /// How many pings didn't get a pong in time.
+    pub lost: u32,
+}
+
+/// Returns round-trip time of a single ping, without parsing the pong.
+/// Waits for the pong for [`DEFAULT_TIMEOUT`].
+///
+/// # Arguments
+///
+/// * `addr` - address of the target server.
+///
+/// # Panics
+///
+/// Function can return an error if:
+///  - couldn't bind a socket
+///  - couldn't send packet to the target server
+///  - server didn't respond in time
+///
+/// # Example
+///
+/// ```no_run
+/// use mcpe_motd::ping;
+///
+/// let latency = ping("127.0.0.1:19132")?;
+///
+/// println!("{} ms", latency.as_millis());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn ping(addr: impl ToSocketAddrs) -> Result<Duration> {
+    let addr = resolve(addr)?;
+
+    match sample_latency(addr, DEFAULT_TIMEOUT)? {
+        Some(latency) => Ok(latency),
+        None => Err(MotdError::new(MotdErrorCode::Timeout, format!("{} didn't respond within {:?}", addr, DEFAULT_TIMEOUT))),
+    }
+}
+
+/// Sends `attempts` pings one after another and reports how many of them got a pong back within `timeout`.
+///
+/// # Arguments
+///
+/// * `addr` - address of the target server.
+/// * `attempts` - how many pings to send.
+/// * `timeout` - how long to wait for each pong (must not be zero).
+///
+/// # Panics
+///
+/// Function can return an error if:
+///  - couldn't bind a socket
+///  - `timeout` is zero (*MotdErrorCode::Timeout*)
+///  - couldn't send packet to the target server
+///
+/// Pongs that didn't arrive in time are counted as lost, not as errors.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use mcpe_motd::ping_quality;
+///
+/// let quality = ping_quality("127.0.0.1:19132", 10, Duration::from_secs(1)).unwrap();
+///
+/// println!("{} packets transmitted, {} received, {:.1}% packet loss", quality.sent, quality.received, quality.loss_pct);
+/// ```
+pub fn ping_quality(addr: impl ToSocketAddrs, attempts: u32, timeout: Duration) -> Result<PingQuality> {
+    let addr = resolve(addr)?;
+
+    let mut received = 0;
+    let mut total_latency = Duration::ZERO;
+
+    for _ in 0..attempts {
+        if let Some(latency) = sample_latency(addr, timeout)? {
+            received += 1;
+            total_latency += latency;
+        }
+    }
+
+    Ok(PingQuality {
+        sent: attempts,
+        received,
+        loss_pct: if attempts == 0 { 0.0 } else { (attempts - received) as f32 * 100.0 / attempts as f32 },
+        mean_latency: if received == 0 { None } else { Some(total_latency / received) },
+    })
+}
+
+/// Sends one ping on a fresh socket and returns round-trip time, or `None` if no pong arrived within `timeout`.
+fn sample_latency(addr: SocketAddr, timeout: Duration) -> Result<Option<Duration>> {
+    let bind_addr = any_addr(addr);
+
+    let socket = match UdpSocket::bind(bind_addr) {
+        Ok(sock) => sock,
+        Err(e) => { return Err(MotdError::new(MotdErrorCode::CantBind, format!("Couldn't bind to {}: {}", bind_addr, e))); }
+    };
+
+    // Exchange reports this as a timeout too, but it's a bad argument rather than a lost pong
+    if timeout.is_zero() {
+        return Err(MotdError::new(MotdErrorCode::Timeout, format!("Couldn't wait for {} with timeout {:?}", addr, timeout)));
+    }
+
+    // Only unconnected pong from the pinged server counts as an answer, it isn't parsed
+    let options = FetchOptions::default().timeout(timeout).drain_stray(true);
+
+    match options.send_and_receive(&socket, addr, false, &mut [0; DEFAULT_BUFFER_SIZE]) {
+        Ok((_, _, latency)) => Ok(Some(latency)),
+        Err(e) if e.code == MotdErrorCode::Timeout => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Sends `count` pings one after another and returns min / max / average round-trip time of the received pongs,
+/// like the summary of the `ping` command.
+///
+/// # Arguments
+///
+/// * `addr` - address of the target server.
+/// * `count` - how many pings to send.
+/// * `timeout` - how long to wait for each pong (must not be zero).
+///
+/// # Panics
+///
+/// Function can return the same errors as [`ping_quality`], pongs that didn't arrive in time are counted in *lost*.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use mcpe_motd::ping_stats;
+///
+/// let stats = ping_stats("127.0.0.1:19132", 10, Duration::from_secs(1))?;
+///
+/// if let (Some(min), Some(avg), Some(max)) = (stats.min, stats.avg, stats.max) {
+///     println!("rtt min/avg/max = {:?}/{:?}/{:?}, {} lost", min, avg, max, stats.lost);
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn ping_stats(addr: impl ToSocketAddrs, count: u32, timeout: Duration) -> Result<LatencyStats> {
+    let addr = resolve(addr)?;
+
+    let mut latencies = Vec::new();
+
+    for _ in 0..count {
+        if let Some(latency) = sample_latency(addr, timeout)? {
+            latencies.push(latency);
+        }
+    }
+
+    Ok(LatencyStats {
+        min: latencies.iter().min().copied(),
+        max: latencies.iter().max().copied(),
+        avg: if latencies.is_empty() { None } else { Some(latencies.iter().sum::<Duration>() / latencies.len() as u32) },
+        lost: count - latencies.len() as u32,
+    })
+}
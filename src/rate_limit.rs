@@ -0,0 +1,82 @@
+//! Limiting how many packets are sent.
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Token bucket limiting how many pings per second are sent.
+/// Clones share the same bucket, so one limiter passed to every worker of a scan caps the whole scan.
+///
+/// # Example
+///
+/// ```no_run
+/// use mcpe_motd::{FetchOptions, RateLimiter, Scanner};
+///
+/// let options = FetchOptions::new().rate_limit(RateLimiter::new(50));
+///
+/// for (addr, result) in Scanner::new(["127.0.0.1:19132", "127.0.0.1:19133"]).options(options) {
+///     println!("{}: {}", addr, result.is_ok());
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    per_second: f64,
+    capacity: f64,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    /// Creates limiter allowing `per_second` packets per second (at least 1), without bursts.
+    pub fn new(per_second: u32) -> Self {
+        RateLimiter {
+            bucket: Arc::new(Mutex::new(Bucket {
+                per_second: per_second.max(1) as f64,
+                capacity: 1.0,
+                tokens: 1.0,
+                refilled_at: Instant::now(),
+            })),
+        }
+    }
+
+    /// Allows up to `burst` packets to be sent at once after being idle (1 by default).
+    pub fn burst(self, burst: u32) -> Self {
+        if let Ok(mut bucket) = self.bucket.lock() {
+            bucket.capacity = burst.max(1) as f64;
+            bucket.tokens = bucket.capacity;
+        }
+
+        self
+    }
+
+    /// Blocks until another packet may be sent.
+    pub fn acquire(&self) {
+        loop {
+            let wait = match self.bucket.lock() {
+                Ok(mut bucket) => {
+                    let now = Instant::now();
+                    let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+
+                    bucket.tokens = (bucket.tokens + elapsed * bucket.per_second).min(bucket.capacity);
+                    bucket.refilled_at = now;
+
+                    if bucket.tokens >= 1.0 {
+                        bucket.tokens -= 1.0;
+                        return;
+                    }
+
+                    Duration::from_secs_f64((1.0 - bucket.tokens) / bucket.per_second)
+                }
+                // Some worker panicked while holding the lock, don't limit anymore rather than panic too
+                Err(_) => return,
+            };
+
+            thread::sleep(wait);
+        }
+    }
+}
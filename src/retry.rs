@@ -0,0 +1,46 @@
+//! Delays between retries.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Returns `base` doubled `attempt` times (0 for the first retry), but not longer than `max`.
+pub(crate) fn backoff(base: Duration, attempt: u32, max: Duration) -> Duration {
+    base.saturating_mul(1 << attempt.min(31)).min(max)
+}
+
+/// Returns `delay` randomly scaled by a factor within `1.0 ± jitter`.
+pub(crate) fn jittered(delay: Duration, jitter: f64) -> Duration {
+    // NaN (or no jitter) keeps the delay as it is
+    if jitter.is_nan() || jitter <= 0.0 {
+        return delay;
+    }
+
+    // Uniform number in [-1.0, 1.0)
+    let offset = (next_random() >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0;
+
+    // `checked_mul_f64` isn't stable, `mul_f64` would panic when a huge delay overflows
+    Duration::try_from_secs_f64(delay.as_secs_f64() * (1.0 + offset * jitter)).unwrap_or(Duration::MAX)
+}
+
+/// Xorshift64* generator shared by the whole process, seeded from the clock on first use.
+pub(crate) fn next_random() -> u64 {
+    static STATE: AtomicU64 = AtomicU64::new(0);
+
+    let mut next = 0;
+
+    // Advanced atomically (the closure reruns if another thread got there first), so concurrent fetches never draw the same number
+    let _ = STATE.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |mut x| {
+        if x == 0 {
+            x = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos() as u64).unwrap_or(0) | 1;
+        }
+
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+
+        next = x;
+        Some(x)
+    });
+
+    next.wrapping_mul(0x2545_f491_4f6c_dd1d)
+}
@@ -0,0 +1,451 @@
+//! Scanning a lot of servers.
+
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::net::{Ipv4Addr, SocketAddr, ToSocketAddrs, UdpSocket};
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::{any_addr, parse_unconnected_pong, Edition, FetchOptions, MotdError, MotdErrorCode, Result, UnconnectedPong, DEFAULT_BUFFER_SIZE, DEFAULT_PORT, UNCONNECTED_PING};
+
+/// How many servers [`fetch_many`] queries at the same time.
+const FETCH_MANY_WORKERS: usize = 64;
+
+/// Lazily fetches [unconnected pongs](UnconnectedPong) for every address of an iterator.
+/// Results are yielded one at a time, so memory usage doesn't depend on how many addresses there are.
+///
+/// To resume a scan that was interrupted, pass the addresses that were already processed to [`Scanner::skip_processed`].
+///
+/// # Example
+///
+/// ```no_run
+/// use std::collections::HashSet;
+/// use mcpe_motd::Scanner;
+///
+/// // Addresses saved by the previous run.
+/// let processed: HashSet<String> = HashSet::from(["127.0.0.1:19132".to_string()]);
+///
+/// let addrs = vec!["127.0.0.1:19132", "127.0.0.1:19133"];
+///
+/// for (addr, result) in Scanner::new(addrs).skip_processed(move |addr| processed.contains(addr)) {
+///     match result {
+///         Ok(pong) => println!("{}: {}", addr, pong.server_id_string_parsed.motd),
+///         Err(e) => println!("{}: {}", addr, e.message),
+///     }
+/// }
+/// ```
+pub struct Scanner<I> {
+    addrs: I,
+    options: FetchOptions,
+    is_processed: Box<dyn FnMut(&str) -> bool>,
+}
+
+impl<I> Scanner<I>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+    /// Creates a scanner over `addrs` with default [`FetchOptions`].
+    pub fn new(addrs: impl IntoIterator<IntoIter = I>) -> Self {
+        Scanner {
+            addrs: addrs.into_iter(),
+            options: FetchOptions::default(),
+            is_processed: Box::new(|_| false),
+        }
+    }
+
+    /// Options used for every fetch.
+    pub fn options(mut self, options: FetchOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Skips every address for which `is_processed` returns `true` (e.g. ones already saved by a previous run).
+    pub fn skip_processed(mut self, is_processed: impl FnMut(&str) -> bool + 'static) -> Self {
+        self.is_processed = Box::new(is_processed);
+        self
+    }
+}
+
+impl<I> Iterator for Scanner<I>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+    type Item = (I::Item, Result<UnconnectedPong>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let addr = self.addrs.next()?;
+
+            if (self.is_processed)(addr.as_ref()) {
+                continue;
+            }
+
+            let result = self.options.fetch(addr.as_ref());
+            return Some((addr, result));
+        }
+    }
+}
+
+/// Fetches [unconnected pongs](UnconnectedPong) of all `addrs` in parallel (up to 64 at a time), so the whole batch takes about as long as the slowest server.
+/// Results are returned in the same order as `addrs`, each with its own error.
+///
+/// # Arguments
+///
+/// * `addrs` - addresses of the target servers.
+/// * `timeout` - how long to wait for each server.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use mcpe_motd::fetch_many;
+///
+/// for (addr, result) in fetch_many(&["127.0.0.1:19132", "127.0.0.1:19133"], Duration::from_secs(3)) {
+///     match result {
+///         Ok(pong) => println!("{}: {}", addr, pong.server_id_string_parsed.motd),
+///         Err(e) => println!("{}: {}", addr, e),
+///     }
+/// }
+/// ```
+pub fn fetch_many(addrs: &[&str], timeout: Duration) -> Vec<(String, Result<UnconnectedPong>)> {
+    fetch_many_with(addrs, &FetchOptions::default().timeout(timeout))
+}
+
+/// Same as [`fetch_many`], but every address is fetched with `options` (e.g. with retries or [`FetchOptions::lenient`]).
+/// A [`FetchOptions::rate_limit`] limiter is shared by all workers, so it limits the whole batch.
+///
+/// # Example
+///
+/// ```no_run
+/// use mcpe_motd::{fetch_many_with, FetchOptions, RateLimiter};
+///
+/// let options = FetchOptions::new().retries(2).rate_limit(RateLimiter::new(100));
+///
+/// for (addr, result) in fetch_many_with(&["127.0.0.1:19132", "127.0.0.1:19133"], &options) {
+///     if let Ok(pong) = result {
+///         println!("{}: {}", addr, pong.server_id_string_parsed.motd);
+///     }
+/// }
+/// ```
+pub fn fetch_many_with(addrs: &[&str], options: &FetchOptions) -> Vec<(String, Result<UnconnectedPong>)> {
+    let results = fetch_parallel(addrs, options);
+
+    addrs.iter().map(|addr| addr.to_string()).zip(results).collect()
+}
+
+/// Queries all `addrs` at the same time (e.g. A and AAAA records or several proxies of one server) and returns the first valid pong
+/// together with the address that sent it. Fetches that are still running are cancelled then.
+///
+/// Unlike [`fetch_many`] every address gets its own thread, so it's meant for a handful of addresses rather than a scan.
+///
+/// # Arguments
+///
+/// * `addrs` - addresses of the same server.
+/// * `timeout` - how long to wait for each address.
+///
+/// # Panics
+///
+/// Function returns an error only if every address failed (or `addrs` is empty). Its code is the one of the first address,
+/// message lists errors of all of them.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use mcpe_motd::fetch_fastest;
+///
+/// let (winner, pong) = fetch_fastest(&["play.example.com:19132", "127.0.0.1:19132"], Duration::from_secs(1))?;
+///
+/// println!("{} answered first: {}", winner, pong.server_id_string_parsed.motd);
+/// # Ok::<(), mcpe_motd::MotdError>(())
+/// ```
+pub fn fetch_fastest(addrs: &[&str], timeout: Duration) -> Result<(String, UnconnectedPong)> {
+    if addrs.is_empty() {
+        return Err(MotdError::new(MotdErrorCode::CantResolveAddress, String::from("No addresses to fetch")));
+    }
+
+    let cancel = Arc::new(AtomicBool::new(false));
+    let options = FetchOptions::default().timeout(timeout).cancel(cancel.clone());
+
+    thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel();
+
+        for (i, addr) in addrs.iter().enumerate() {
+            let sender = sender.clone();
+            let options = &options;
+
+            scope.spawn(move || {
+                let _ = sender.send((i, options.fetch(addr)));
+            });
+        }
+
+        // Receiving ends once every fetch has reported
+        drop(sender);
+
+        let mut errors: Vec<(usize, MotdError)> = Vec::new();
+
+        for (i, result) in receiver {
+            match result {
+                Ok(pong) => {
+                    // The rest notice it within a few ms, scope waits for them
+                    cancel.store(true, Ordering::Relaxed);
+                    return Ok((addrs[i].to_string(), pong));
+                }
+                Err(e) => errors.push((i, e)),
+            }
+        }
+
+        errors.sort_by_key(|(i, _)| *i);
+
+        let message = errors.iter().map(|(i, e)| format!("{}: {}", addrs[*i], e.message)).collect::<Vec<_>>().join("; ");
+
+        Err(MotdError::new(errors[0].1.code, format!("Every address failed ({})", message)))
+    })
+}
+
+/// Queries every port of `ports` on `ip` in parallel (like [`fetch_many`]) and returns only the ones that answered with a valid pong,
+/// e.g. to discover all Bedrock instances running on one host.
+///
+/// # Arguments
+///
+/// * `ip` - ip or hostname of the host.
+/// * `ports` - ports to query.
+/// * `timeout` - how long to wait for each port.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use mcpe_motd::scan_ports;
+///
+/// for (port, pong) in scan_ports("127.0.0.1", 19132..=19140, Duration::from_secs(1)) {
+///     println!("{}: {}", port, pong.server_id_string_parsed.motd);
+/// }
+/// ```
+pub fn scan_ports(ip: &str, ports: RangeInclusive<u16>, timeout: Duration) -> Vec<(u16, UnconnectedPong)> {
+    scan_ports_with(ip, ports, &FetchOptions::default().timeout(timeout))
+}
+
+/// Same as [`scan_ports`], but every port is queried with `options`, see [`fetch_many_with`].
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use mcpe_motd::{scan_ports_with, FetchOptions, RateLimiter};
+///
+/// let options = FetchOptions::new().timeout(Duration::from_secs(1)).rate_limit(RateLimiter::new(50));
+///
+/// for (port, pong) in scan_ports_with("127.0.0.1", 19132..=19140, &options) {
+///     println!("{}: {}", port, pong.server_id_string_parsed.motd);
+/// }
+/// ```
+pub fn scan_ports_with(ip: &str, ports: RangeInclusive<u16>, options: &FetchOptions) -> Vec<(u16, UnconnectedPong)> {
+    let addrs: Vec<(&str, u16)> = ports.map(|port| (ip, port)).collect();
+    let results = fetch_parallel(&addrs, options);
+
+    addrs.iter()
+        .zip(results)
+        .filter_map(|((_, port), result)| result.ok().map(|pong| (*port, pong)))
+        .collect()
+}
+
+/// Finds servers of the local network the way the game does: broadcasts a ping to `255.255.255.255:19132`
+/// and collects every valid pong that arrives within `timeout`, together with the address it came from.
+///
+/// Only the first pong of each address is kept. Errors (e.g. broadcasting isn't allowed) end the discovery,
+/// returning whatever was received so far. To see servers as soon as they answer use [`discover_lan_iter`].
+///
+/// # Arguments
+///
+/// * `timeout` - how long to listen for pongs.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use mcpe_motd::discover_lan;
+///
+/// for (addr, pong) in discover_lan(Duration::from_secs(2)) {
+///     println!("{}: {}", addr, pong.server_id_string_parsed.motd);
+/// }
+/// ```
+pub fn discover_lan(timeout: Duration) -> Vec<(SocketAddr, UnconnectedPong)> {
+    let mut servers = Vec::new();
+
+    discover_lan_iter(timeout, |addr, pong| servers.push((addr, pong)));
+
+    servers
+}
+
+/// Same as [`discover_lan`], but calls `on_pong` for every server right when its pong arrives
+/// (e.g. to fill a server list in a UI while still listening). Returns after `timeout`.
+///
+/// # Arguments
+///
+/// * `timeout` - how long to listen for pongs.
+/// * `on_pong` - called with the address and the pong of every server found.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use mcpe_motd::discover_lan_iter;
+///
+/// discover_lan_iter(Duration::from_secs(2), |addr, pong| {
+///     println!("{}: {}", addr, pong.server_id_string_parsed.motd);
+/// });
+/// ```
+pub fn discover_lan_iter(timeout: Duration, on_pong: impl FnMut(SocketAddr, UnconnectedPong)) {
+    discover_lan_iter_on(SocketAddr::from((Ipv4Addr::BROADCAST, DEFAULT_PORT)), timeout, on_pong)
+}
+
+/// Same as [`discover_lan_iter`], but the ping is sent to `broadcast` instead of `255.255.255.255:19132`
+/// (e.g. a subnet broadcast address like `192.168.1.255:19132`, or servers on another port).
+///
+/// Pongs that fill the whole receive buffer ([`FetchOptions::buffer_size`] by default, 2048 bytes) were probably cut off, so they are skipped.
+///
+/// # Arguments
+///
+/// * `broadcast` - address the ping is sent to.
+/// * `timeout` - how long to listen for pongs.
+/// * `on_pong` - called with the address and the pong of every server found.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use mcpe_motd::discover_lan_iter_on;
+///
+/// discover_lan_iter_on("192.168.1.255:19132".parse().unwrap(), Duration::from_secs(1), |addr, pong| {
+///     println!("{}: {}", addr, pong.server_id_string_parsed.motd);
+/// });
+/// ```
+pub fn discover_lan_iter_on(broadcast: SocketAddr, timeout: Duration, mut on_pong: impl FnMut(SocketAddr, UnconnectedPong)) {
+    let socket = match UdpSocket::bind(any_addr(broadcast)) {
+        Ok(sock) => sock,
+        Err(e) => {
+            warn!("couldn't bind lan discovery socket: {}", e);
+            return;
+        }
+    };
+
+    if let Err(e) = socket.set_broadcast(true) {
+        warn!("couldn't enable broadcast: {}", e);
+        return;
+    }
+
+    let sent_at = Instant::now();
+
+    if let Err(e) = socket.send_to(&UNCONNECTED_PING, broadcast) {
+        warn!("couldn't broadcast ping: {}", e);
+        return;
+    }
+
+    let deadline = sent_at + timeout;
+    let mut response = [0; DEFAULT_BUFFER_SIZE];
+    let mut seen: Vec<SocketAddr> = Vec::new();
+
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+
+        // Zero timeout can't be set on a socket
+        if remaining.is_zero() || socket.set_read_timeout(Some(remaining)).is_err() {
+            return;
+        }
+
+        let (size, src) = match socket.recv_from(&mut response) {
+            Ok(v) => v,
+            Err(e) if e.kind() == ErrorKind::WouldBlock || e.kind() == ErrorKind::TimedOut => return,
+            Err(e) => {
+                warn!("couldn't receive lan pong: {}", e);
+                return;
+            }
+        };
+
+        if seen.contains(&src) {
+            continue;
+        }
+
+        // Datagrams longer than the buffer are silently cut off, a truncated pong isn't reported as a server
+        if size == response.len() {
+            warn!("lan pong filled the whole buffer: src={} bytes={}", src, size);
+            continue;
+        }
+
+        match parse_unconnected_pong(&response[..size]) {
+            Ok(mut pong) => {
+                debug!("discovered lan server: src={} bytes={}", src, size);
+
+                pong.source_addr = Some(src);
+                pong.latency = sent_at.elapsed();
+                pong.received_at = SystemTime::now();
+
+                seen.push(src);
+                on_pong(src, pong);
+            }
+            Err(e) => {
+                debug!("ignored lan packet: src={} bytes={} message={}", src, size, e.message);
+            }
+        }
+    }
+}
+
+/// Fetches all `addrs` with `options` on up to [`FETCH_MANY_WORKERS`] threads, results are in the same order as `addrs`.
+fn fetch_parallel<A: ToSocketAddrs + Sync>(addrs: &[A], options: &FetchOptions) -> Vec<Result<UnconnectedPong>> {
+    let next = AtomicUsize::new(0);
+
+    let mut results: Vec<(usize, Result<UnconnectedPong>)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..addrs.len().min(FETCH_MANY_WORKERS))
+            .map(|_| scope.spawn(|| {
+                let mut results = Vec::new();
+
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+
+                    match addrs.get(i) {
+                        Some(addr) => results.push((i, options.fetch(addr))),
+                        None => return results,
+                    }
+                }
+            }))
+            .collect();
+
+        // Worker can only panic if fetch itself did, so pass it on
+        workers.into_iter().flat_map(|worker| worker.join().unwrap_or_else(|e| std::panic::resume_unwind(e))).collect()
+    });
+
+    results.sort_by_key(|(i, _)| *i);
+
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Groups successful results (e.g. from [`Scanner`]) by server [`Edition`], failed ones are skipped.
+///
+/// # Example
+///
+/// ```no_run
+/// use mcpe_motd::{group_by_edition, Scanner};
+///
+/// let by_edition = group_by_edition(Scanner::new(["127.0.0.1:19132", "127.0.0.1:19133"]));
+///
+/// for (edition, servers) in &by_edition {
+///     println!("{}: {} servers", edition, servers.len());
+/// }
+/// ```
+pub fn group_by_edition<S>(results: impl IntoIterator<Item = (S, Result<UnconnectedPong>)>) -> HashMap<Edition, Vec<(S, UnconnectedPong)>> {
+    let mut groups: HashMap<Edition, Vec<(S, UnconnectedPong)>> = HashMap::new();
+
+    for (addr, result) in results {
+        if let Ok(pong) = result {
+            groups.entry(pong.server_id_string_parsed.edition.clone()).or_default().push((addr, pong));
+        }
+    }
+
+    groups
+}
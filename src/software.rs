@@ -0,0 +1,94 @@
+//! Guessing which software a server runs.
+
+use crate::ServerIdStringParsed;
+
+/// Names of the featured server networks (lowercase), see [`ServerIdStringParsed::is_featured_server`].
+const FEATURED_NETWORKS: &[&str] = &["the hive", "cubecraft", "lifeboat", "mineville", "galaxite", "pixel paradise"];
+
+/// Featured networks advertise huge capacities, community servers reusing their names usually don't.
+const FEATURED_MIN_MAX_PLAYERS: i32 = 1000;
+
+/// Server software guessed by [`ServerIdStringParsed::detect_software`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ServerSoftware {
+    /// Vanilla Bedrock Dedicated Server (or something that imitates it well).
+    Bedrock,
+    /// PocketMine-MP.
+    PocketMine,
+    /// Nukkit (and its forks).
+    Nukkit,
+    /// Nothing matched.
+    Unknown,
+}
+
+impl ServerIdStringParsed {
+    /// Best-effort guess of the server software. It's a heuristic: servers can put anything into their pong, so don't rely on it.
+    ///
+    /// Names in the motd or level name (e.g. "PocketMine-MP") are checked first, then the shape of server id string:
+    /// PocketMine sends 9 fields, Nukkit sends 10 and vanilla sends all 12 with a numeric server unique id.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcpe_motd::{parse_server_id_string, ServerSoftware};
+    ///
+    /// let parsed = parse_server_id_string("MCPE;Dedicated Server;615;1.20.30;3;10;13253860892328930865;Bedrock level;Survival;1;19132;19133;").unwrap();
+    /// assert_eq!(parsed.detect_software(), ServerSoftware::Bedrock);
+    ///
+    /// let parsed = parse_server_id_string("MCPE;My server;615;1.20.30;3;10;1234;PocketMine-MP;Survival;").unwrap();
+    /// assert_eq!(parsed.detect_software(), ServerSoftware::PocketMine);
+    ///
+    /// let parsed = parse_server_id_string("MCPE;My server;615;1.20.30;3;10;-1234;Powered by Nukkit;Survival;1").unwrap();
+    /// assert_eq!(parsed.detect_software(), ServerSoftware::Nukkit);
+    ///
+    /// let parsed = parse_server_id_string("MCPE;My server;615;1.20.30").unwrap();
+    /// assert_eq!(parsed.detect_software(), ServerSoftware::Unknown);
+    /// ```
+    pub fn detect_software(&self) -> ServerSoftware {
+        let names = [self.motd.to_lowercase(), self.level_name.to_lowercase()];
+
+        if names.iter().any(|name| name.contains("pocketmine")) {
+            return ServerSoftware::PocketMine;
+        }
+
+        if names.iter().any(|name| name.contains("nukkit")) {
+            return ServerSoftware::Nukkit;
+        }
+
+        match self.field_count {
+            9 => ServerSoftware::PocketMine,
+            10 => ServerSoftware::Nukkit,
+            12.. if self.server_unique_id_numeric().is_some() => ServerSoftware::Bedrock,
+            _ => ServerSoftware::Unknown,
+        }
+    }
+
+    /// Best-effort guess whether it's one of the featured servers (big networks listed in the game, e.g. The Hive or CubeCraft).
+    /// It's a heuristic and the list of networks may change between versions.
+    ///
+    /// To keep community servers from being mislabeled, both have to match: motd (without formatting) or level name
+    /// contains the name of a featured network, and *max_player_count* is at least 1000. Anything else is `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcpe_motd::parse_server_id_string;
+    ///
+    /// let parsed = parse_server_id_string("MCPE;§bCubeCraft §7Games;618;1.20.40;14000;55000").unwrap();
+    /// assert!(parsed.is_featured_server());
+    ///
+    /// // Same name, but a small server
+    /// let parsed = parse_server_id_string("MCPE;CubeCraft remake;618;1.20.40;3;20").unwrap();
+    /// assert!(!parsed.is_featured_server());
+    /// ```
+    pub fn is_featured_server(&self) -> bool {
+        if self.max_player_count < FEATURED_MIN_MAX_PLAYERS {
+            return false;
+        }
+
+        let names = [self.motd_plain().to_lowercase(), self.level_name.to_lowercase()];
+
+        names.iter().any(|name| FEATURED_NETWORKS.iter().any(|network| name.contains(network)))
+    }
+}
@@ -0,0 +1,352 @@
+//! Tests doctests can't do: against fake servers on localhost and of crate internals.
+
+use std::net::{SocketAddr, UdpSocket};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::{
+    check_connectable, discover_lan_iter_on, fetch_fastest, fetch_many_with, fetch_unconected_pong_timeout, monitor, ping_stats, scan_ports_with, FetchOptions, MotdErrorCode, ParseWarning,
+    RAKNET_MAGIC,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Pong answering `ping` (its time and magic echoed) with `server_id_string`.
+fn pong(ping: &[u8], server_id_string: &[u8]) -> Vec<u8> {
+    let mut pong = vec![0x1c];
+    pong.extend_from_slice(&ping[1..9]);
+    pong.extend_from_slice(&[0; 8]);
+    pong.extend_from_slice(&ping[9..25]);
+    pong.extend_from_slice(&(server_id_string.len() as u16).to_be_bytes());
+    pong.extend_from_slice(server_id_string);
+    pong
+}
+
+/// Starts a server on a random port of `ip` sending back what `answer` returns for every packet it gets.
+fn fake_server(ip: &str, mut answer: impl FnMut(&[u8]) -> Vec<Vec<u8>> + Send + 'static) -> SocketAddr {
+    let server = UdpSocket::bind((ip, 0)).unwrap();
+    let addr = server.local_addr().unwrap();
+
+    thread::spawn(move || {
+        let mut request = [0; 2048];
+
+        while let Ok((size, client)) = server.recv_from(&mut request) {
+            for packet in answer(&request[..size]) {
+                server.send_to(&packet, client).unwrap();
+            }
+        }
+    });
+
+    addr
+}
+
+/// Fake server answering every ping with `server_id_string`.
+fn pong_server(server_id_string: &'static [u8]) -> SocketAddr {
+    fake_server("127.0.0.1", move |ping| vec![pong(ping, server_id_string)])
+}
+
+#[test]
+fn buffer_size_truncated() {
+    let addr = fake_server("127.0.0.1", |_| vec![vec![0x1c; 100]]);
+
+    let e = FetchOptions::new().buffer_size(64).timeout(TIMEOUT).fetch(addr).unwrap_err();
+
+    assert_eq!(e.code, MotdErrorCode::PacketTruncated);
+}
+
+#[test]
+fn strict_utf8_invalid() {
+    let addr = pong_server(b"MCPE;Broken \xff motd;615;1.20.30;0;10");
+
+    let e = FetchOptions::new().strict_utf8(true).timeout(TIMEOUT).fetch(addr).unwrap_err();
+
+    assert_eq!(e.code, MotdErrorCode::InvalidUtf8);
+}
+
+#[test]
+fn infer_string_length_without_length() {
+    let addr = fake_server("127.0.0.1", |ping| {
+        let mut pong = pong(ping, b"");
+        pong.truncate(33);
+        pong.extend_from_slice(b"MCPE;No length;615;1.20.30;0;10");
+        vec![pong]
+    });
+
+    let pong = FetchOptions::new().timeout(TIMEOUT).infer_string_length(true).fetch(addr).unwrap();
+
+    assert_eq!(pong.server_id_string_parsed.motd, "No length");
+    assert_eq!(pong.server_id_string_len, 31);
+}
+
+#[test]
+fn lenient_invalid_gamemode() {
+    let addr = pong_server(b"MCPE;Lenient;615;1.20.30;3;10;1;world;Creative;creative;19132;19133");
+
+    let pong = FetchOptions::new().timeout(TIMEOUT).lenient(true).fetch(addr).unwrap();
+
+    assert_eq!(pong.server_id_string_parsed.player_count, 3);
+    assert_eq!(pong.server_id_string_parsed.gamemode_numeric, 0);
+    assert_eq!(pong.warnings, [ParseWarning::InvalidGamemodeNumeric]);
+}
+
+#[test]
+fn strict_missing_field() {
+    let addr = pong_server(b"MCPE;Dedicated Server;615;1.20.30;0;10");
+
+    let e = FetchOptions::new().strict(true).timeout(TIMEOUT).fetch(addr).unwrap_err();
+
+    assert_eq!(e.code, MotdErrorCode::MissingField);
+    assert!(e.message.contains("server_unique_id"));
+}
+
+#[test]
+fn verify_roundtrip_padded() {
+    let addr = pong_server(b"MCPE;Padded;615;1.20.30; 3;10;1;world;Survival;1;19132;19133");
+
+    let pong = FetchOptions::new().timeout(TIMEOUT).verify_roundtrip(true).fetch(addr).unwrap();
+
+    assert_eq!(pong.server_id_string_parsed.player_count, 3);
+    assert_eq!(pong.warnings, [ParseWarning::RoundtripMismatch]);
+    assert!(!pong.server_id_string_parsed_ok);
+}
+
+#[test]
+fn verify_roundtrip_trailing_semicolon() {
+    let addr = pong_server(b"MCPE;Dedicated Server;615;1.20.30;3;10;13253860892328930865;Bedrock level;Survival;1;19132;19133;");
+
+    let pong = FetchOptions::new().timeout(TIMEOUT).verify_roundtrip(true).fetch(addr).unwrap();
+
+    assert_eq!(pong.warnings, []);
+    assert!(pong.server_id_string_parsed_ok);
+}
+
+#[test]
+fn return_raw_on_error_keeps_packet() {
+    let addr = fake_server("127.0.0.1", |_| vec![vec![0x1c, 0x00, 0x01]]);
+
+    let e = FetchOptions::new().timeout(TIMEOUT).return_raw_on_error(true).fetch(addr).unwrap_err();
+
+    assert_eq!(e.code, MotdErrorCode::PacketTooShort);
+    assert_eq!(e.raw_bytes, Some(vec![0x1c, 0x00, 0x01]));
+}
+
+#[test]
+fn trust_queried_port_replaces_ports() {
+    let addr = pong_server(b"MCPE;Proxied;615;1.20.30;0;10;1;world;Survival;1;19133;19134");
+
+    let pong = FetchOptions::new().timeout(TIMEOUT).trust_queried_port(true).fetch(addr).unwrap();
+
+    assert_eq!(pong.server_id_string_parsed.port_v4, addr.port());
+    assert_eq!(pong.server_id_string_parsed.port_v6, addr.port());
+}
+
+#[test]
+fn client_guid_is_sent() {
+    // Server puts the guid it received into the motd
+    let addr = fake_server("127.0.0.1", |ping| {
+        let guid = u64::from_be_bytes(ping[25..33].try_into().unwrap());
+        vec![pong(ping, format!("MCPE;{:x};615;1.20.30;0;10", guid).as_bytes())]
+    });
+
+    let pong = FetchOptions::new().client_guid(0xdeadbeef).timeout(TIMEOUT).fetch(addr).unwrap();
+
+    assert_eq!(pong.server_id_string_parsed.motd, "deadbeef");
+}
+
+#[test]
+fn ping_time_is_echoed() {
+    let addr = pong_server(b"MCPE;Dedicated Server;615;1.20.30;0;10");
+
+    let pong = FetchOptions::new().ping_time(42).timeout(TIMEOUT).fetch(addr).unwrap();
+
+    assert_eq!(pong.time_since_start, 42);
+}
+
+#[test]
+fn custom_magic() {
+    let magic = [0x42; 16];
+    let addr = fake_server("127.0.0.1", move |ping| {
+        let mut pong = pong(ping, b"MCPE;Modded server;615;1.20.30;0;10");
+        pong[17..33].copy_from_slice(&magic);
+        vec![pong]
+    });
+
+    let pong = FetchOptions::new().magic(magic).timeout(TIMEOUT).fetch(addr).unwrap();
+    assert_eq!(pong.magic, magic);
+
+    let e = FetchOptions::new().timeout(TIMEOUT).fetch(addr).unwrap_err();
+    assert_eq!(e.code, MotdErrorCode::InvalidMagic);
+}
+
+#[test]
+fn drain_stray_skips_other_packets() {
+    let addr = fake_server("127.0.0.1", |ping| vec![vec![0x15, 0, 0, 0], pong(ping, b"MCPE;Drained;615;1.20.30;0;10")]);
+
+    let pong = FetchOptions::new().timeout(TIMEOUT).drain_stray(true).fetch(addr).unwrap();
+
+    assert_eq!(pong.server_id_string_parsed.motd, "Drained");
+}
+
+#[test]
+fn fetch_with_socket_ignores_other_addresses() {
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let client = socket.local_addr().unwrap();
+
+    // Somebody else sends a pong before the queried server answers
+    let spoofer = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let addr = fake_server("127.0.0.1", move |ping| {
+        spoofer.send_to(&pong(ping, b"MCPE;Spoofed;615;1.20.30;0;10"), client).unwrap();
+        vec![pong(ping, b"MCPE;Real;615;1.20.30;0;10")]
+    });
+
+    let pong = FetchOptions::new().timeout(TIMEOUT).fetch_with_socket(&socket, addr).unwrap();
+
+    assert_eq!(pong.server_id_string_parsed.motd, "Real");
+}
+
+#[test]
+fn fetch_connected_socket() {
+    let addr = pong_server(b"MCPE;Peer;615;1.20.30;0;10");
+
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    socket.connect(addr).unwrap();
+
+    let pong = FetchOptions::new().timeout(TIMEOUT).fetch_connected(&socket).unwrap();
+
+    assert_eq!(pong.server_id_string_parsed.motd, "Peer");
+    assert_eq!(pong.source_addr, Some(addr));
+}
+
+#[test]
+fn fetch_ipv6() {
+    let addr = fake_server("::1", |ping| vec![pong(ping, b"MCPE;IPv6 server;615;1.20.30;0;10")]);
+
+    let pong = fetch_unconected_pong_timeout(addr, TIMEOUT).unwrap();
+
+    assert_eq!(pong.server_id_string_parsed.motd, "IPv6 server");
+}
+
+#[test]
+fn fetch_fastest_skips_dead_address() {
+    let addr = pong_server(b"MCPE;Fastest;615;1.20.30;0;10").to_string();
+
+    // Nothing listens on the first address
+    let (winner, pong) = fetch_fastest(&["127.0.0.1:9", &addr], TIMEOUT).unwrap();
+
+    assert_eq!(winner, addr);
+    assert_eq!(pong.server_id_string_parsed.motd, "Fastest");
+}
+
+#[test]
+fn fetch_many_with_options() {
+    let addr = pong_server(b"MCPE;Lenient;615;1.20.30;3;10;1;world;Creative;creative;19132;19133").to_string();
+
+    let results = fetch_many_with(&[&addr, &addr], &FetchOptions::new().timeout(TIMEOUT).lenient(true));
+
+    assert_eq!(results.len(), 2);
+    for (_, result) in results {
+        assert_eq!(result.unwrap().warnings, [ParseWarning::InvalidGamemodeNumeric]);
+    }
+}
+
+#[test]
+fn scan_ports_with_options() {
+    let addr = pong_server(b"MCPE;Lenient;615;1.20.30;3;10;1;world;Creative;creative;19132;19133");
+
+    let found = scan_ports_with("127.0.0.1", addr.port()..=addr.port(), &FetchOptions::new().timeout(TIMEOUT).lenient(true));
+    assert_eq!(found.len(), 1);
+
+    // Without lenient the gamemode is an error, so nothing is found
+    let found = scan_ports_with("127.0.0.1", addr.port()..=addr.port(), &FetchOptions::new().timeout(TIMEOUT));
+    assert!(found.is_empty());
+}
+
+#[test]
+fn ping_stats_ignores_stray_packets() {
+    let addr = fake_server("127.0.0.1", |ping| vec![vec![0x15, 0, 0, 0], pong(ping, b"not parsed")]);
+
+    let stats = ping_stats(addr, 3, TIMEOUT).unwrap();
+
+    assert_eq!(stats.lost, 0);
+    assert!(stats.min.is_some());
+}
+
+#[test]
+fn ping_stats_counts_silence_as_lost() {
+    let silent = UdpSocket::bind("127.0.0.1:0").unwrap();
+
+    let stats = ping_stats(silent.local_addr().unwrap(), 2, Duration::from_millis(50)).unwrap();
+
+    assert_eq!(stats.lost, 2);
+    assert_eq!(stats.avg, None);
+}
+
+#[test]
+fn monitor_stops_before_window_ends() {
+    let addr = pong_server(b"MCPE;Monitored;615;1.20.30;3;10;1;world;Survival;1;19132;19133");
+
+    // Polls start at 0, 100 and 200 ms of the 250 ms window, the next one would start after it
+    let stats = monitor(addr, Duration::from_millis(100), Duration::from_millis(250));
+
+    assert_eq!(stats.polls, 3);
+    assert_eq!(stats.successful, 3);
+    assert_eq!(stats.avg_players, Some(3.0));
+}
+
+#[test]
+fn discover_lan_skips_truncated_pongs() {
+    // Pong as large as the receive buffer first, then a normal one
+    let addr = fake_server("127.0.0.1", |ping| {
+        let oversized = format!("MCPE;{};615;1.20.30;1;8", "a".repeat(2048 - 35 - 21));
+        vec![pong(ping, oversized.as_bytes()), pong(ping, b"MCPE;Lan world;615;1.20.30;1;8;1;world;Survival;1;19132;19133")]
+    });
+
+    let mut found = Vec::new();
+    discover_lan_iter_on(addr, Duration::from_millis(500), |_, pong| found.push(pong.server_id_string_parsed.motd));
+
+    assert_eq!(found, ["Lan world"]);
+}
+
+#[test]
+fn check_connectable_reply() {
+    let addr = fake_server("127.0.0.1", |request| {
+        assert_eq!(request[0], 0x05);
+
+        let mut reply = vec![0x06];
+        reply.extend_from_slice(&RAKNET_MAGIC);
+        reply.extend_from_slice(&1234u64.to_be_bytes());
+        reply.push(0);
+        reply.extend_from_slice(&1492u16.to_be_bytes());
+        vec![reply]
+    });
+
+    assert!(check_connectable(addr, TIMEOUT).unwrap());
+}
+
+#[test]
+fn jitter_huge_delay() {
+    for _ in 0..100 {
+        crate::retry::jittered(Duration::MAX, 1.0);
+    }
+}
+
+#[test]
+fn check_connectable_retry_keeps_timeout() {
+    // Server asks for another protocol version late, then doesn't answer the second request
+    let addr = fake_server("127.0.0.1", |request| {
+        if request[17] == 10 {
+            return Vec::new();
+        }
+
+        thread::sleep(Duration::from_millis(250));
+
+        let mut reply = vec![0x19, 10];
+        reply.extend_from_slice(&RAKNET_MAGIC);
+        reply.extend_from_slice(&1234u64.to_be_bytes());
+        vec![reply]
+    });
+
+    let start = Instant::now();
+    assert!(!check_connectable(addr, Duration::from_millis(300)).unwrap());
+    assert!(start.elapsed() < Duration::from_millis(450));
+}
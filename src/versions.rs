@@ -0,0 +1,98 @@
+//! Known Bedrock protocol versions.
+
+use crate::ServerIdStringParsed;
+
+/// Protocol versions and names of the releases that introduced them.
+/// Needs to be updated when new versions are released.
+const PROTOCOL_VERSIONS: &[(u16, &str)] = &[
+    (527, "1.19.0"),
+    (534, "1.19.10"),
+    (544, "1.19.20"),
+    (545, "1.19.21"),
+    (554, "1.19.30"),
+    (557, "1.19.40"),
+    (560, "1.19.50"),
+    (567, "1.19.60"),
+    (568, "1.19.63"),
+    (575, "1.19.70"),
+    (582, "1.19.80"),
+    (589, "1.20.0"),
+    (594, "1.20.10"),
+    (618, "1.20.30"),
+    (622, "1.20.40"),
+    (630, "1.20.50"),
+    (649, "1.20.60"),
+    (662, "1.20.70"),
+    (671, "1.20.80"),
+    (685, "1.21.0"),
+    (686, "1.21.2"),
+    (712, "1.21.20"),
+    (729, "1.21.30"),
+    (748, "1.21.40"),
+    (766, "1.21.50"),
+    (776, "1.21.60"),
+    (786, "1.21.70"),
+    (800, "1.21.80"),
+];
+
+/// Returns name of the Bedrock version using `protocol` (e.g. 618 → "1.20.30"), or `None` if it isn't known.
+///
+/// The table only covers releases since 1.19.0 and has to be updated as new versions come out,
+/// so a newer server may have a protocol that isn't there yet.
+///
+/// # Example
+///
+/// ```
+/// use mcpe_motd::protocol_to_version_name;
+///
+/// assert_eq!(protocol_to_version_name(618), Some("1.20.30"));
+/// assert_eq!(protocol_to_version_name(1), None);
+/// ```
+pub fn protocol_to_version_name(protocol: u16) -> Option<&'static str> {
+    PROTOCOL_VERSIONS.iter().find(|(known, _)| *known == protocol).map(|(_, name)| *name)
+}
+
+/// Whether `name` can't be a release using `protocol`, only if `protocol` is in the table and `name` looks like a version.
+///
+/// Hotfixes keep the protocol of their release (1.20.30 - 1.20.32 all use 618),
+/// so any name from the release up to the one of the next known protocol matches.
+pub(crate) fn version_mismatch(protocol: u16, name: &str) -> bool {
+    let Some(index) = PROTOCOL_VERSIONS.iter().position(|(known, _)| *known == protocol) else {
+        return false;
+    };
+
+    let Some(reported) = version_numbers(name) else {
+        return false;
+    };
+
+    let after_release = version_numbers(PROTOCOL_VERSIONS[index].1).is_some_and(|release| reported >= release);
+    let before_next = PROTOCOL_VERSIONS.get(index + 1).and_then(|(_, next)| version_numbers(next)).is_none_or(|next| reported < next);
+
+    !(after_release && before_next)
+}
+
+/// Splits "1.20.30" into [1, 20, 30], `None` if it isn't a dotted version.
+fn version_numbers(name: &str) -> Option<Vec<u32>> {
+    name.trim().split('.').map(|part| part.parse().ok()).collect()
+}
+
+impl ServerIdStringParsed {
+    /// *version_name* if server sent it, otherwise the name looked up by *protocol_version* (see [`protocol_to_version_name`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcpe_motd::ServerIdStringParsed;
+    ///
+    /// let parsed = ServerIdStringParsed { protocol_version: 618, ..Default::default() };
+    ///
+    /// assert_eq!(parsed.version_name_or_known(), Some("1.20.30"));
+    /// ```
+    pub fn version_name_or_known(&self) -> Option<&str> {
+        if self.version_name.is_empty() {
+            protocol_to_version_name(self.protocol_version)
+        } else {
+            Some(&self.version_name)
+        }
+    }
+}